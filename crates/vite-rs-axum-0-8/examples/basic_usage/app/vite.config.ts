@@ -8,9 +8,4 @@ export default defineConfig({
       input: ["index.html"],
     },
   },
-  server: {
-    hmr: {
-      port: 21012,
-    },
-  },
 });
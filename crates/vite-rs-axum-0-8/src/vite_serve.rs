@@ -32,7 +32,8 @@ impl Clone for ViteServe {
 }
 
 /// Caching strategies specify how the server sets the Control-Cache header.
-/// In development, we always send 'no-cache' to ensure the latest files are served.
+/// In development, the `Cache-Control` header sent by the ViteJS dev server is forwarded as-is
+/// unless a strategy other than `None` is set.
 #[derive(Clone)]
 pub enum CacheStrategy {
     /// Always up-to-date. Checks for new updates before serving files.
@@ -52,6 +53,10 @@ pub enum CacheStrategy {
 
 impl ViteServe {
     pub fn new(assets: Box<dyn GetFromVite>) -> Self {
+        // the browser opens Vite's HMR websocket on our origin, and we tunnel it through
+        #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+        assets.set_hmr_proxied();
+
         Self {
             #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
             cache_strategy: CacheStrategy::None,
@@ -76,109 +81,213 @@ impl ViteServe {
     where
         B: axum::body::HttpBody<Data = axum::body::Bytes> + Send + 'static,
     {
-        // Extract the path from the request, removing the leading slash
-        let path = req.uri().path().trim_start_matches('/');
-        let query = req
-            .uri()
-            .query()
-            .map(|q| format!("?{}", q))
-            .unwrap_or_default();
-
-        // If the path is empty, default to index.html
-        let request_file_path = if path.is_empty() { "index.html" } else { path };
-
-        match self.assets.get(&format!("{}{}", request_file_path, query)) {
-            Some(file) => {
-                let mut response = Response::builder();
+        #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+        let response = {
+            // The request body isn't forwarded to the ViteJS dev server
+            let (parts, _) = req.into_parts();
+            let req = axum::http::Request::from_parts(parts, ());
 
-                response = response.header("Content-Type", file.content_type);
-                response = response.header("Content-Length", file.content_length);
+            if is_upgrade_request(req.headers()) {
+                self.serve_upgrade_from_dev_server(req).await
+            } else {
+                self.serve_from_dev_server(req).await
+            }
+        };
 
-                let etag = {
-                    #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
-                    {
-                        file.content_hash
-                    }
+        #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+        let response = {
+            // Extract the path from the request, removing the leading slash
+            let path = req.uri().path().trim_start_matches('/');
+            let query = req
+                .uri()
+                .query()
+                .map(|q| format!("?{}", q))
+                .unwrap_or_default();
 
-                    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-                    {
-                        &file.content_hash
-                    }
-                };
+            // If the path is empty, default to index.html
+            let request_file_path = if path.is_empty() { "index.html" } else { path };
 
-                response = response.status(200).header("ETag", etag);
+            self.serve_embedded(req.headers(), &format!("{}{}", request_file_path, query))
+        };
 
-                match self.cache_strategy {
-                    CacheStrategy::Eager => {
-                        response = response.header("Cache-Control", "max-age=0, must-revalidate");
-                    }
-                    CacheStrategy::Lazy => {
-                        response = response
-                            .header("Cache-Control", "max-age=0, stale-while-revalidate=604800");
-                    }
-                    CacheStrategy::None => {
-                        response = response.header("Cache-Control", "no-cache");
-                    }
-                    CacheStrategy::Custom(header) => {
-                        response = response.header("Cache-Control", header);
-                    }
-                };
+        response
+    }
+
+    /// Serves a file embedded in the binary.
+    ///
+    /// Its `ETag` is strong (the quoted content hash, see `ViteFile::etag`), and requests whose
+    /// `If-None-Match` matches it get `304 Not Modified`.
+    ///
+    /// Responds with `404 Not Found` for a missing file (unless the SPA fallback applies), and with
+    /// `502 Bad Gateway` if the file exists but the assets couldn't retrieve it.
+    #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+    fn serve_embedded(
+        &self,
+        req_headers: &axum::http::HeaderMap,
+        request_file_path: &str,
+    ) -> Response {
+        match self.assets.try_get(request_file_path) {
+            Ok(file) => {
+                let mut response = Response::builder();
+
+                response = response.header("Content-Type", &*file.content_type);
+                response = response.header("Content-Length", file.content_length);
+
+                let etag = &*file.etag;
+
+                response = response
+                    .status(200)
+                    .header("ETag", etag)
+                    .header("Cache-Control", self.cache_control());
 
-                if let Some(last_modified) = file.last_modified {
-                    response = response.header("Last-Modified", last_modified);
+                if let Some(last_modified) = &file.last_modified {
+                    response = response.header("Last-Modified", &**last_modified);
                 }
 
-                match req.headers().get(axum::http::header::IF_NONE_MATCH) {
-                    Some(header) => {
-                        let header_etag = header.to_str().expect(
-                            "Could not read IF_NONE_MATCH header, it contained invalid characters.",
-                        );
-
-                        if etag.eq(header_etag) {
-                            // If the ETag matches, return 304 Not Modified
-                            #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-                            return response.status(304).body(Body::from(vec![])).unwrap();
-
-                            #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
-                            return response.status(304).body(Body::from(&[][..])).unwrap();
-                        } else {
-                            // If it doesn't match, return the full response
-                            return response.body(Body::from(file.bytes)).unwrap();
-                        }
-                    }
-                    None => {
-                        // If no IF_NONE_MATCH header, return the full response
-                        return response.body(Body::from(file.bytes)).unwrap();
-                    }
+                if is_not_modified(req_headers, etag) {
+                    // If the ETag matches, return 304 Not Modified
+                    return response.status(304).body(Body::empty()).unwrap();
                 }
+
+                response.body(Body::from(file.bytes)).unwrap()
             }
-            None => {
-                match self.fallback_strategy {
-                    FallbackStrategy::SinglePageApplication(ref fallback_file) => {
-                        if let Some(index) = self.assets.get(fallback_file) {
-                            return Response::builder()
-                                .status(200)
-                                .header("Content-Type", index.content_type)
-                                .header("Content-Length", index.content_length)
-                                .body(Body::from(index.bytes))
-                                .unwrap();
-                        }
+            Err(vite_rs_interface::ViteError::NotFound) => {
+                if let FallbackStrategy::SinglePageApplication(ref fallback_file) =
+                    self.fallback_strategy
+                {
+                    if let Ok(index) = self.assets.try_get(fallback_file) {
+                        return Response::builder()
+                            .status(200)
+                            .header("Content-Type", &*index.content_type)
+                            .header("Content-Length", index.content_length)
+                            .body(Body::from(index.bytes))
+                            .unwrap();
                     }
-                    FallbackStrategy::NotFound => { /* logic below returns 404 as required */ }
                 }
 
-                #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-                return Response::builder()
-                    .status(404)
-                    .body(Body::from(vec![]))
-                    .unwrap();
+                Response::builder().status(404).body(Body::empty()).unwrap()
+            }
+            // The file exists but couldn't be retrieved
+            Err(_) => Response::builder().status(502).body(Body::empty()).unwrap(),
+        }
+    }
 
-                #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
-                return Response::builder()
-                    .status(404)
-                    .body(Body::from(&[][..]))
+    /// Proxies a request to the ViteJS dev server.
+    ///
+    /// The request's headers are forwarded, and the dev server's status, headers and body are
+    /// returned as if the client had hit the dev server directly. If the dev server is
+    /// unreachable, responds with `502 Bad Gateway`.
+    ///
+    /// The dev server's `ETag`s are weak (`W/"..."`), since it transforms files on each request;
+    /// it answers `If-None-Match` itself. This is the one exception to `ViteFile::etag` being
+    /// strong: asset sources without a dev server (e.g. `InMemoryAssets`) respond with strong
+    /// `ETag`s, like in release builds.
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    async fn serve_from_dev_server(&self, req: axum::http::Request<()>) -> Response {
+        let fallback_req = match self.fallback_strategy {
+            FallbackStrategy::SinglePageApplication(ref fallback_file) => {
+                let mut fallback_req = axum::http::Request::get(format!("/{}", fallback_file))
+                    .body(())
                     .unwrap();
+                *fallback_req.headers_mut() = req.headers().clone();
+                // the fallback file is a different resource; its ETag won't match
+                fallback_req
+                    .headers_mut()
+                    .remove(axum::http::header::IF_NONE_MATCH);
+
+                Some(fallback_req)
+            }
+            FallbackStrategy::NotFound => None,
+        };
+
+        let mut res = match self.assets.proxy(req).await {
+            Some(res) => res,
+            None => return Response::builder().status(502).body(Body::empty()).unwrap(),
+        };
+
+        if res.status() == axum::http::StatusCode::NOT_FOUND {
+            if let Some(fallback_req) = fallback_req {
+                match self.assets.proxy(fallback_req).await {
+                    Some(fallback_res) if fallback_res.status().is_success() => res = fallback_res,
+                    _ => { /* respond with the original 404 */ }
+                }
             }
         }
+
+        let (mut parts, body) = res.into_parts();
+
+        if !matches!(self.cache_strategy, CacheStrategy::None) {
+            parts.headers.insert(
+                axum::http::header::CACHE_CONTROL,
+                axum::http::HeaderValue::from_static(self.cache_control()),
+            );
+        }
+
+        Response::from_parts(parts, Body::from_stream(body))
+    }
+
+    /// Tunnels a protocol upgrade (Vite's HMR websocket) through to the ViteJS dev server, so
+    /// that the browser only needs to talk to this server's origin.
+    ///
+    /// Once the dev server switches protocols, bytes are copied between the client's and the
+    /// dev server's connections until either side closes.
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    async fn serve_upgrade_from_dev_server(&self, mut req: axum::http::Request<()>) -> Response {
+        let client_upgrade = hyper::upgrade::on(&mut req);
+
+        let (head, upgraded) = match self.assets.proxy_upgrade(req).await {
+            Some(res) => res,
+            None => return Response::builder().status(502).body(Body::empty()).unwrap(),
+        };
+
+        if let Some(mut upgraded) = upgraded {
+            tokio::spawn(async move {
+                // the client went away before switching protocols; there's nothing to tunnel
+                let Ok(client) = client_upgrade.await else {
+                    return;
+                };
+
+                let mut client = hyper_util::rt::TokioIo::new(client);
+                // errors here are disconnects; either side closing ends the tunnel
+                let _ = tokio::io::copy_bidirectional(&mut client, &mut upgraded).await;
+            });
+        }
+
+        let (parts, ()) = head.into_parts();
+        Response::from_parts(parts, Body::empty())
+    }
+
+    /// The `Cache-Control` header value for the current cache strategy.
+    fn cache_control(&self) -> &'static str {
+        match self.cache_strategy {
+            CacheStrategy::Eager => "max-age=0, must-revalidate",
+            CacheStrategy::Lazy => "max-age=0, stale-while-revalidate=604800",
+            CacheStrategy::None => "no-cache",
+            CacheStrategy::Custom(header) => header,
+        }
     }
 }
+
+/// Whether the request asks to switch protocols (e.g. `Connection: Upgrade` + `Upgrade: websocket`).
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn is_upgrade_request(req_headers: &axum::http::HeaderMap) -> bool {
+    let connection_upgrade = req_headers
+        .get_all(axum::http::header::CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|token| token.trim().eq_ignore_ascii_case("upgrade"));
+
+    connection_upgrade && req_headers.contains_key(axum::http::header::UPGRADE)
+}
+
+/// Whether the request's `If-None-Match` header matches the file's ETag (see `etag_matches`). An
+/// unreadable header never matches.
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+fn is_not_modified(req_headers: &axum::http::HeaderMap, etag: &str) -> bool {
+    req_headers
+        .get_all(axum::http::header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .any(|if_none_match| vite_rs_interface::etag_matches(if_none_match, etag))
+}
@@ -32,12 +32,18 @@ async fn test() {
     }));
 
     #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-    std::thread::sleep(std::time::Duration::from_secs(2)); // wait for dev server to start
+    _guard
+        .as_ref()
+        .unwrap()
+        .wait_ready_async(std::time::Duration::from_secs(30))
+        .await
+        .expect("ViteJS dev server did not start");
 
     for app in [app_with_fallback_service, app_with_mounted_service] {
         ensure_serves_index(app()).await;
         ensure_serves_public_files(app()).await;
         ensure_serves_imports(app()).await;
+        ensure_serves_concurrent_requests(app()).await;
     }
 
     test_default_cache_strategy().await; // includes `Eager` and `None` depending on build type (release/debug)
@@ -45,6 +51,10 @@ async fn test() {
     test_custom_cache_strategy().await;
 
     test_cache_response().await;
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    test_dev_server_proxy_passthrough().await;
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    test_hmr_websocket_proxy().await;
 
     test_fallback_not_found().await;
     test_fallback_spa_found().await;
@@ -91,6 +101,97 @@ async fn test_cache_response() {
     assert_eq!(response2.status(), StatusCode::NOT_MODIFIED);
 }
 
+/// In dev, requests are proxied to the ViteJS dev server as-is: request headers reach Vite
+/// and its status codes and response headers reach the client.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+async fn test_dev_server_proxy_passthrough() {
+    let app = app_with_fallback_service();
+
+    let req = http::Request::builder()
+        .uri("/script.tsx")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get("Content-Type")
+            .map(|h| h.to_str().unwrap()),
+        Some("text/javascript")
+    );
+
+    // Vite's weak ETag is forwarded untouched (the documented exception to `ViteFile::etag`
+    // being strong)...
+    let etag = response
+        .headers()
+        .get("Etag")
+        .map(|h| h.to_str().unwrap().to_string())
+        .unwrap();
+    assert!(etag.starts_with("W/"));
+
+    // ...and `If-None-Match` reaches Vite, which answers with 304 itself.
+    let req2 = http::Request::builder()
+        .uri("/script.tsx")
+        .header("If-None-Match", &etag)
+        .body(Body::empty())
+        .unwrap();
+
+    let response2 = app.oneshot(req2).await.unwrap();
+    assert_eq!(response2.status(), StatusCode::NOT_MODIFIED);
+}
+
+/// In dev, Vite's HMR websocket is proxied through `ViteServe`, so the browser never needs to
+/// talk to the dev server's port directly.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+async fn test_hmr_websocket_proxy() {
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    // Vite only accepts HMR connections with the token it embeds in its client script
+    let request = http::Request::builder()
+        .uri("/@vite/client")
+        .body(Body::empty())
+        .unwrap();
+    let response = app_with_fallback_service().oneshot(request).await.unwrap();
+    let client_script = body::to_bytes(response.into_body(), 262144).await.unwrap();
+    let client_script = std::str::from_utf8(&client_script).unwrap();
+    let ws_token = client_script
+        .split("const wsToken = \"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .expect("Could not find the HMR token in Vite's client script.");
+
+    // Upgrades need a real connection, so we serve the app on a free port
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app_with_fallback_service())
+            .await
+            .unwrap();
+    });
+
+    let mut ws_request = format!("ws://{addr}/?token={ws_token}")
+        .into_client_request()
+        .unwrap();
+    ws_request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", "vite-hmr".parse().unwrap());
+
+    let (mut ws, response) = tokio_tungstenite::connect_async(ws_request)
+        .await
+        .expect("Failed to connect to Vite's HMR websocket through ViteServe.");
+    assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+    // Vite greets every HMR client
+    let message = ws.next().await.unwrap().unwrap();
+    assert_eq!(
+        message.into_text().unwrap().as_str(),
+        "{\"type\":\"connected\"}"
+    );
+}
+
 async fn test_custom_cache_strategy() {
     // custom cache strategy
     let app = axum::Router::new().route_service(
@@ -255,6 +356,30 @@ async fn ensure_serves_imports(app: axum::Router) {
     assert!(body_bytes.starts_with(b"(function(){const vl=document.createElement(\"link\").relList;if(vl&&vl.supports&&vl.supports(\"modulepreload\"))return;for(const Q of document.querySelectorAll('link[rel=\"modulepreload\"]'))r(Q);new MutationObserver(Q=>{for(const L of Q)if(L.type===\"childList\")for(const tl of L.addedNodes)tl.tagName===\"LINK\"&&tl.rel===\"modulepreload\"&&r(tl)}).observe(document,{childList:!0,subtree:!0});function J(Q){const L={};return Q.integrity&&(L.integrity=Q.integrity),Q.referrerPolicy&&(L.referrerPolicy=Q.referrerPolicy),Q.crossOrigin===\"use-credentials\"?L.credentials=\"include\":Q.crossOrigin===\"anonymous\"?L.credentials=\"omit\":L.credentials=\"same-origin\",L}function r(Q){if(Q.ep)return;Q.ep=!0;const L=J(Q);fetch(Q.href,L)}})();const R1=\"modulepreload\",H1=function(_){return\"/\"+_},wv={},N1=function(vl,J,r){let Q=Promise.resolve();if(J&&J.length>0){let tl=function(T){return Promise.all(T.map(U=>Promise.resolve(U).then(k=>({status:\"fulfilled\",value:k}),k=>({status:\"rejected\",reason:k}))))};document.getElementsByTagName(\"link\""));
 }
 
+/// Many ES modules are requested at once on page load; in dev, these are streamed from the
+/// ViteJS dev server over a shared connection pool instead of a thread per request.
+async fn ensure_serves_concurrent_requests(app: axum::Router) {
+    let requests = (0..100).map(|_| {
+        let app = app.clone();
+        tokio::spawn(async move {
+            let request = http::Request::builder()
+                .uri("/test.css")
+                .body(Body::empty())
+                .unwrap();
+
+            app.oneshot(request).await.unwrap()
+        })
+    });
+
+    for request in requests.collect::<Vec<_>>() {
+        let response = request.await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = body::to_bytes(response.into_body(), 2048).await.unwrap();
+        assert!(body_bytes.starts_with(b"body {"));
+    }
+}
+
 /// FallbackStrategy::NotFound returns 404 with an empty body for unknown paths.
 ///
 /// In dev builds the Vite dev server itself handles every path and returns index.html (200), so
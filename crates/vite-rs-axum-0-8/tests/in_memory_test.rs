@@ -0,0 +1,94 @@
+use reqwest::StatusCode;
+use tower::ServiceExt;
+use vite_rs::{GetFromVite, InMemoryAssets};
+use vite_rs_axum_0_8::{FallbackStrategy, ViteServe};
+
+use axum::{
+    body::{self, Body},
+    http,
+};
+
+// `InMemoryAssets` serves the same way in dev and release builds, without a ViteJS dev server.
+fn assets() -> InMemoryAssets {
+    InMemoryAssets::builder()
+        .insert("index.html", "<h1>hello</h1>")
+        .insert("assets/main-4f2a.js", "console.log('hello');")
+        .alias("src/main.ts", "assets/main-4f2a.js")
+        .build()
+}
+
+async fn get(serve: ViteServe, uri: &str) -> axum::response::Response {
+    let app = axum::Router::new().fallback_service(serve);
+    let request = http::Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn serves_files() {
+    let response = get(ViteServe::new(Box::new(assets())), "/").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["Content-Type"], "text/html");
+    assert_eq!(response.headers()["Content-Length"], "14");
+    assert!(response.headers().contains_key("Last-Modified"));
+    let body_bytes = body::to_bytes(response.into_body(), 256).await.unwrap();
+    assert_eq!(body_bytes, "<h1>hello</h1>");
+
+    let response = get(ViteServe::new(Box::new(assets())), "/assets/main-4f2a.js").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["Content-Type"], "application/javascript");
+}
+
+#[tokio::test]
+async fn serves_aliases() {
+    let response = get(ViteServe::new(Box::new(assets())), "/src/main.ts").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = body::to_bytes(response.into_body(), 256).await.unwrap();
+    assert_eq!(body_bytes, "console.log('hello');");
+}
+
+#[tokio::test]
+async fn falls_back() {
+    let response = get(ViteServe::new(Box::new(assets())), "/missing.txt").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let serve = ViteServe::new(Box::new(assets()))
+        .with_fallback_strategy(FallbackStrategy::SinglePageApplication("index.html".into()));
+    let response = get(serve, "/some/client/side/route").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = body::to_bytes(response.into_body(), 256).await.unwrap();
+    assert_eq!(body_bytes, "<h1>hello</h1>");
+}
+
+/// The files have the same strong ETag in dev and release builds.
+#[tokio::test]
+async fn responds_not_modified() {
+    let response = get(ViteServe::new(Box::new(assets())), "/index.html").await;
+    let etag = response.headers()["ETag"].to_str().unwrap().to_string();
+    assert_eq!(etag, assets().get("index.html").unwrap().etag);
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+    let status = |if_none_match: String| async move {
+        let app = axum::Router::new().fallback_service(ViteServe::new(Box::new(assets())));
+        let request = http::Request::builder()
+            .uri("/index.html")
+            .header("If-None-Match", if_none_match)
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    };
+
+    assert_eq!(status(etag.clone()).await, StatusCode::NOT_MODIFIED);
+    // If-None-Match uses the weak comparison, and can list several ETags
+    assert_eq!(
+        status(format!("W/{}", etag)).await,
+        StatusCode::NOT_MODIFIED
+    );
+    assert_eq!(
+        status(format!("\"other\", {}", etag)).await,
+        StatusCode::NOT_MODIFIED
+    );
+    assert_eq!(status("\"other\"".to_string()).await, StatusCode::OK);
+}
@@ -0,0 +1,134 @@
+//! Starting the ViteJS dev server with settings only known at runtime.
+
+use crate::{DevServerConfig, DevServerError, ViteProcess};
+
+/// Builder for starting the ViteJS dev server with environment variables only known at runtime,
+/// e.g. an API URL read from the app's config. They're set on top of the `#[env]` attributes.
+///
+/// Returned by the derived `dev_server()` method:
+///
+/// ```ignore
+/// #[cfg(debug_assertions)]
+/// let _guard = Assets::dev_server()
+///     .env("VITE_API_URL", format!("http://localhost:{}/api", port))
+///     .start(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DevServerBuilder {
+    config: DevServerConfig,
+    env: Vec<(String, String)>,
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    capture_logs: Option<bool>,
+}
+
+impl DevServerBuilder {
+    pub fn new(config: DevServerConfig) -> Self {
+        Self {
+            config,
+            env: Vec::new(),
+            #[cfg(any(feature = "log", feature = "tracing"))]
+            capture_logs: None,
+        }
+    }
+
+    /// Sets an environment variable for the dev server.
+    ///
+    /// Note: Vite only exposes variables prefixed with `VITE_` to your client code
+    /// (see Vite's `envPrefix` option).
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets several environment variables for the dev server; see `env`.
+    pub fn envs<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env.extend(
+            vars.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    /// Sets whether this dev server's output is forwarded to the `log`/`tracing` facade,
+    /// overriding `set_capture_logs`.
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    pub fn capture_logs(mut self, enabled: bool) -> Self {
+        self.capture_logs = Some(enabled);
+        self
+    }
+
+    /// Starts the ViteJS dev server; see `start_dev_server`.
+    ///
+    /// Panics if the dev server can't be started; use `try_start` to handle the error instead.
+    pub fn start(
+        self,
+        #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+    ) -> Option<ViteProcess> {
+        match self.try_start(
+            #[cfg(feature = "ctrlc")]
+            register_ctrl_c_handler,
+        ) {
+            Ok(process) => Some(process),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Starts the ViteJS dev server; see `try_start_dev_server`.
+    ///
+    /// The environment variables have no effect on a dev server that's already running
+    /// (or a remote one).
+    pub fn try_start(
+        self,
+        #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+    ) -> Result<ViteProcess, DevServerError> {
+        crate::start(
+            &self.config,
+            self.env,
+            #[cfg(feature = "ctrlc")]
+            register_ctrl_c_handler,
+            #[cfg(any(feature = "log", feature = "tracing"))]
+            self.capture_logs.unwrap_or_else(crate::logs::capture_logs),
+        )
+    }
+
+    /// Starts the ViteJS dev server without blocking the async runtime; see `start_dev_server_async`.
+    #[cfg(feature = "tokio")]
+    pub async fn start_async(
+        self,
+        #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+    ) -> Option<ViteProcess> {
+        match self
+            .try_start_async(
+                #[cfg(feature = "ctrlc")]
+                register_ctrl_c_handler,
+            )
+            .await
+        {
+            Ok(process) => Some(process),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Starts the ViteJS dev server without blocking the async runtime; see
+    /// `try_start_dev_server_async`.
+    #[cfg(feature = "tokio")]
+    pub async fn try_start_async(
+        self,
+        #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+    ) -> Result<ViteProcess, DevServerError> {
+        crate::start_async(
+            &self.config,
+            self.env,
+            #[cfg(feature = "ctrlc")]
+            register_ctrl_c_handler,
+            #[cfg(any(feature = "log", feature = "tracing"))]
+            self.capture_logs.unwrap_or_else(crate::logs::capture_logs),
+        )
+        .await
+    }
+}
@@ -0,0 +1,225 @@
+//! HTTP clients used by derived code to fetch files from the ViteJS dev server.
+//!
+//! Both clients are created once and shared so that connections to the dev server are pooled;
+//! a page that loads hundreds of ES modules shouldn't open hundreds of TCP connections.
+
+use futures_util::StreamExt;
+
+lazy_static::lazy_static! {
+    static ref CLIENT: reqwest::Client = reqwest::Client::new();
+    static ref BLOCKING_CLIENT: reqwest::blocking::Client = reqwest::blocking::Client::new();
+}
+
+/// Shared async client. Requests must be polled from within a tokio runtime.
+pub fn client() -> &'static reqwest::Client {
+    &CLIENT
+}
+
+/// Shared blocking client.
+///
+/// Note: `reqwest::blocking` panics when it is created or used from within an async runtime,
+/// so callers should only touch it from a thread that isn't driving one.
+pub fn blocking_client() -> &'static reqwest::blocking::Client {
+    &BLOCKING_CLIENT
+}
+
+/// Body stream of a dev server response; same shape as `vite_rs_interface::ViteFileBody`.
+pub type BodyStream = std::pin::Pin<
+    Box<
+        dyn futures_util::Stream<
+                Item = Result<bytes::Bytes, Box<dyn std::error::Error + Send + Sync>>,
+            > + Send,
+    >,
+>;
+
+/// Forwards the body of a dev server response chunk by chunk.
+pub fn body_stream(res: reqwest::Response) -> BodyStream {
+    Box::pin(
+        res.bytes_stream().map(|chunk| {
+            chunk.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        }),
+    )
+}
+
+/// Why a file couldn't be fetched from the ViteJS dev server. Derived code turns it into the
+/// matching `vite_rs_interface::ViteError`.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The dev server responded with `404 Not Found`.
+    NotFound,
+    /// The request couldn't be sent, e.g. the dev server isn't running.
+    Unreachable(reqwest::Error),
+    /// The dev server responded with an error status (other than `404 Not Found`).
+    Status(http::StatusCode),
+    /// The dev server didn't send a header it's expected to send.
+    MissingHeader(http::HeaderName),
+}
+
+/// A successful dev server response for a file, and its `Content-Type`.
+pub struct FileResponse<R> {
+    pub response: R,
+    pub content_type: String,
+}
+
+/// Fetches `path` from the ViteJS dev server at `base_url` with the shared blocking client.
+///
+/// See `blocking_client` for why this must not be called from within an async runtime.
+pub fn fetch_file_blocking(
+    base_url: &str,
+    path: &str,
+) -> Result<FileResponse<reqwest::blocking::Response>, FetchError> {
+    let response = blocking_client()
+        .get(file_url(base_url, path))
+        .send()
+        .map_err(FetchError::Unreachable)?;
+
+    let content_type = check_file_response(response.status(), response.headers())?;
+
+    Ok(FileResponse {
+        response,
+        content_type,
+    })
+}
+
+/// Fetches `path` from the ViteJS dev server at `base_url` with the shared async client.
+pub async fn fetch_file(
+    base_url: &str,
+    path: &str,
+) -> Result<FileResponse<reqwest::Response>, FetchError> {
+    let response = client()
+        .get(file_url(base_url, path))
+        .send()
+        .await
+        .map_err(FetchError::Unreachable)?;
+
+    let content_type = check_file_response(response.status(), response.headers())?;
+
+    Ok(FileResponse {
+        response,
+        content_type,
+    })
+}
+
+/// Reads a header the dev server is expected to send.
+fn expect_header(headers: &http::HeaderMap, name: http::HeaderName) -> Result<String, FetchError> {
+    match headers.get(&name).and_then(|value| value.to_str().ok()) {
+        Some(value) => Ok(value.to_string()),
+        None => Err(FetchError::MissingHeader(name)),
+    }
+}
+
+fn file_url(base_url: &str, path: &str) -> String {
+    format!("{}/{}", base_url, path)
+}
+
+/// Maps the status of a dev server response for a file (`404 Not Found` and other errors are
+/// told apart), and reads its `Content-Type`.
+fn check_file_response(
+    status: http::StatusCode,
+    headers: &http::HeaderMap,
+) -> Result<String, FetchError> {
+    if status == http::StatusCode::NOT_FOUND {
+        return Err(FetchError::NotFound);
+    }
+
+    if !status.is_success() {
+        return Err(FetchError::Status(status));
+    }
+
+    expect_header(headers, http::header::CONTENT_TYPE)
+}
+
+/// Headers that only apply to a single connection and must not be forwarded by proxies.
+/// See RFC 9110, section 7.6.1.
+const HOP_BY_HOP_HEADERS: [http::HeaderName; 8] = [
+    http::header::CONNECTION,
+    http::HeaderName::from_static("keep-alive"),
+    http::header::PROXY_AUTHENTICATE,
+    http::header::PROXY_AUTHORIZATION,
+    http::header::TE,
+    http::header::TRAILER,
+    http::header::TRANSFER_ENCODING,
+    http::header::UPGRADE,
+];
+
+/// Removes hop-by-hop headers. When `keep_upgrade` is set, `Connection` and `Upgrade` are kept
+/// so that a protocol upgrade (e.g. to a websocket) can be negotiated through the proxy.
+fn remove_hop_by_hop_headers(headers: &mut http::HeaderMap, keep_upgrade: bool) {
+    for header in HOP_BY_HOP_HEADERS.iter() {
+        if keep_upgrade && (header == http::header::CONNECTION || header == http::header::UPGRADE) {
+            continue;
+        }
+
+        headers.remove(header);
+    }
+}
+
+/// Builds the request forwarded to the ViteJS dev server at `base_url`.
+fn forward_request(
+    base_url: &str,
+    request: http::Request<()>,
+    keep_upgrade: bool,
+) -> reqwest::RequestBuilder {
+    let (parts, ()) = request.into_parts();
+
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or("/");
+    let url = format!("{}{}", base_url.trim_end_matches('/'), path_and_query);
+
+    let mut request_headers = parts.headers;
+    remove_hop_by_hop_headers(&mut request_headers, keep_upgrade);
+    // the dev server's host is set by the client
+    request_headers.remove(http::header::HOST);
+
+    client().request(parts.method, url).headers(request_headers)
+}
+
+/// Copies the status and headers of a dev server response.
+fn response_head(res: &reqwest::Response, keep_upgrade: bool) -> http::response::Builder {
+    let mut response = http::Response::builder().status(res.status());
+    if let Some(response_headers) = response.headers_mut() {
+        response_headers.extend(res.headers().clone());
+        remove_hop_by_hop_headers(response_headers, keep_upgrade);
+    }
+
+    response
+}
+
+/// Forwards a request to the ViteJS dev server at `base_url` (e.g. `http://localhost:21012`)
+/// and returns its response as-is: the status code, headers and (streamed) body are untouched
+/// apart from hop-by-hop headers.
+pub async fn proxy(
+    base_url: &str,
+    request: http::Request<()>,
+) -> reqwest::Result<http::Response<BodyStream>> {
+    let res = forward_request(base_url, request, false).send().await?;
+
+    Ok(response_head(&res, false)
+        .body(body_stream(res))
+        .expect("Failed to build response from ViteJS dev server response."))
+}
+
+/// Forwards a protocol upgrade request (e.g. Vite's HMR websocket) to the ViteJS dev server
+/// at `base_url`.
+///
+/// Returns the dev server's response head and, if it switched protocols, the upgraded
+/// connection. The caller is expected to tunnel the client's upgraded connection through it.
+pub async fn proxy_upgrade(
+    base_url: &str,
+    request: http::Request<()>,
+) -> reqwest::Result<(http::Response<()>, Option<reqwest::Upgraded>)> {
+    let res = forward_request(base_url, request, true).send().await?;
+
+    let head = response_head(&res, true)
+        .body(())
+        .expect("Failed to build response from ViteJS dev server response.");
+
+    if res.status() != http::StatusCode::SWITCHING_PROTOCOLS {
+        return Ok((head, None));
+    }
+
+    Ok((head, Some(res.upgrade().await?)))
+}
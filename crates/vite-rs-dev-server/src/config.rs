@@ -0,0 +1,110 @@
+//! Where the ViteJS dev server for a `#[derive(vite_rs::Embed)]` struct runs.
+//!
+//! The settings come from the struct's attributes, and can be overridden at runtime with
+//! environment variables (e.g. to reach a dev server running in another container).
+
+use crate::port;
+
+/// Environment variable that overrides the host the ViteJS dev server binds to.
+pub const HOST_ENV_VAR: &str = "VITE_RS_DEV_SERVER_HOST";
+
+/// Environment variable that points vite-rs at a remote ViteJS dev server,
+/// e.g. `https://vite.example.com`.
+pub const URL_ENV_VAR: &str = "VITE_RS_DEV_SERVER_URL";
+
+/// Default host the ViteJS dev server binds to.
+pub const DEFAULT_HOST: &str = "localhost";
+
+/// Dev server settings from the `#[derive(vite_rs::Embed)]` attributes.
+///
+/// Note: this is constructed by the derived code; you shouldn't need to use it directly.
+#[derive(Debug, Clone, Copy)]
+pub struct DevServerConfig {
+    /// The `#[root]` directory the dev server runs in.
+    pub absolute_root_dir: &'static str,
+    /// The `#[dev_server_host]` attribute.
+    pub host: Option<&'static str>,
+    /// The `#[dev_server_port]` attribute.
+    pub port: Option<u16>,
+    /// The `#[dev_server_url]` attribute.
+    pub url: Option<&'static str>,
+    /// Environment variables passed to Vite: the `#[env]` attributes, followed by the
+    /// `#[forward_env]` values.
+    pub env: &'static [(&'static str, &'static str)],
+}
+
+impl DevServerConfig {
+    /// The host the dev server binds to: `VITE_RS_DEV_SERVER_HOST`, `#[dev_server_host]`,
+    /// or `localhost`.
+    pub fn host(&self) -> String {
+        non_empty_env(HOST_ENV_VAR)
+            .or_else(|| self.host.map(str::to_string))
+            .unwrap_or_else(|| DEFAULT_HOST.to_string())
+    }
+
+    /// The port the dev server is serving from; see `vite_rs_dev_server::dev_server_port`.
+    pub fn port(&self) -> u16 {
+        port::dev_server_port(self.absolute_root_dir, self.port)
+    }
+
+    /// The URL of a remote dev server: `VITE_RS_DEV_SERVER_URL` or `#[dev_server_url]`.
+    ///
+    /// vite-rs doesn't manage the lifecycle of a remote dev server.
+    pub fn remote_url(&self) -> Option<String> {
+        non_empty_env(URL_ENV_VAR)
+            .or_else(|| self.url.map(str::to_string))
+            .map(|url| url.trim_end_matches('/').to_string())
+    }
+
+    /// Whether a dev server for this project is running on its port (e.g. one started by hand);
+    /// see `vite_rs_dev_server::is_vite_dev_server_running`.
+    ///
+    /// Always `false` for a remote dev server, since its files can't be checked.
+    pub fn is_dev_server_running(&self) -> bool {
+        self.remote_url().is_none()
+            && crate::is_vite_dev_server_running(
+                self.absolute_root_dir,
+                &connect_host(&self.host()),
+                self.port(),
+            )
+    }
+
+    /// The URL requests to the dev server are sent to, without a trailing slash.
+    pub fn base_url(&self) -> String {
+        match self.remote_url() {
+            Some(url) => url,
+            None => format!(
+                "http://{}:{}",
+                url_host(&connect_host(&self.host())),
+                self.port()
+            ),
+        }
+    }
+}
+
+/// The host to connect to a dev server bound to `host`.
+///
+/// A dev server bound to all interfaces can't be connected to via the unspecified address
+/// on every platform, so we connect through the loopback interface instead.
+pub(crate) fn connect_host(host: &str) -> String {
+    match host {
+        "0.0.0.0" | "::" | "[::]" => DEFAULT_HOST.to_string(),
+        host => host.to_string(),
+    }
+}
+
+/// Wraps IPv6 addresses in brackets so they can be used in URLs.
+pub(crate) fn url_host(host: &str) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    }
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
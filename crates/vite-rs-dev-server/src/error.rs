@@ -0,0 +1,96 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Returned by `ViteProcess::wait_ready` when the ViteJS dev server didn't become ready.
+#[derive(Debug)]
+pub enum WaitReadyError {
+    /// The dev server process exited before it started accepting connections.
+    /// Contains the exit status, if it could be determined.
+    Exited(Option<std::process::ExitStatus>),
+    /// The dev server didn't start accepting connections within the given timeout.
+    TimedOut(Duration),
+}
+
+impl fmt::Display for WaitReadyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitReadyError::Exited(Some(status)) => write!(
+                f,
+                "ViteJS dev server exited before it was ready ({})",
+                status
+            ),
+            WaitReadyError::Exited(None) => {
+                write!(f, "ViteJS dev server exited before it was ready")
+            }
+            WaitReadyError::TimedOut(timeout) => {
+                write!(f, "ViteJS dev server was not ready after {:?}", timeout)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WaitReadyError {}
+
+/// Returned by `try_start_dev_server` when the ViteJS dev server couldn't be started.
+#[derive(Debug)]
+pub enum DevServerError {
+    /// `npx` could not be found; is Node.js installed and in your `PATH`?
+    NpxNotFound,
+    /// The dev server's port is already in use, and not by a dev server for this project.
+    PortInUse(u16),
+    /// No port was configured, and none of the ports in the given range are free.
+    NoFreePort(std::ops::Range<u16>),
+    /// The remote dev server URL (`#[dev_server_url]` or `VITE_RS_DEV_SERVER_URL`) is invalid.
+    InvalidUrl(String),
+    /// The dev server process could not be spawned.
+    SpawnFailed(std::io::Error),
+    /// The Ctrl-C handler could not be registered, usually because another one is already set.
+    #[cfg(feature = "ctrlc")]
+    CtrlCHandler(ctrlc::Error),
+}
+
+impl fmt::Display for DevServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DevServerError::NpxNotFound => write!(
+                f,
+                "Could not find `npx`; ensure Node.js is installed and `npx` is in your PATH"
+            ),
+            DevServerError::PortInUse(port) => write!(
+                f,
+                "Selected vite-rs dev server port '{}' is not available (and no ViteJS dev server for this project is running on it).\na) If self-selecting a port via #[dev_server_port = XXX] or the VITE_RS_DEV_SERVER_PORT environment variable, ensure it is free.\nb) Otherwise, remove the #[dev_server_port] attribute and let vite-rs select a free port for you when the dev server starts.",
+                port
+            ),
+            DevServerError::NoFreePort(range) => write!(
+                f,
+                "Could not find a free port for the ViteJS dev server in {:?}; set one with #[dev_server_port = XXX] or the VITE_RS_DEV_SERVER_PORT environment variable",
+                range
+            ),
+            DevServerError::InvalidUrl(url) => write!(
+                f,
+                "Invalid ViteJS dev server URL '{}'; expected something like 'https://vite.example.com'",
+                url
+            ),
+            DevServerError::SpawnFailed(e) => {
+                write!(f, "Failed to start ViteJS dev server: {}", e)
+            }
+            #[cfg(feature = "ctrlc")]
+            DevServerError::CtrlCHandler(e) => write!(
+                f,
+                "vite-rs: Error setting Ctrl-C handler ({}); if you are using a custom one, disable the ctrlc feature for the vite-rs crate, and follow the documentation here to integrate it: https://github.com/Wulf/vite-rs#ctrl-c-handler",
+                e
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DevServerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DevServerError::SpawnFailed(e) => Some(e),
+            #[cfg(feature = "ctrlc")]
+            DevServerError::CtrlCHandler(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Listing of the files served by the ViteJS dev server.
+//!
+//! There's no build output to list in development, and the dev server serves (nearly) every file
+//! of the project. So that the listing matches release builds, we only list what a build starts
+//! from: the project's entry points, at their path relative to the root (release builds resolve
+//! them through aliases), and the files in the public directory (`public/`, Vite's default
+//! `publicDir`), at the path they're served from. Files that are only reached through an entry
+//! point (scripts, styles, images) are compiled and renamed by a build, so they aren't listed.
+//!
+//! Vite's config can't be evaluated from Rust, so the entry points are the files of the project
+//! it names (which is how `build.rollupOptions.input` lists them), or `index.html` (Vite's
+//! default) if it names none.
+
+use std::path::{Component, Path};
+
+use crate::hmr::CONFIG_FILES;
+
+/// Directory whose files Vite serves at the root path.
+const PUBLIC_DIR: &str = "public";
+
+/// Vite's default entry point.
+const DEFAULT_ENTRY_POINT: &str = "index.html";
+
+/// Files at the root of a project that describe it, rather than being part of the app.
+const PROJECT_FILES: [&str; 12] = [
+    "package.json",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "pnpm-workspace.yaml",
+    "bun.lock",
+    "bun.lockb",
+    "deno.json",
+    "deno.lock",
+    "Cargo.toml",
+    "Cargo.lock",
+];
+
+/// Lists the paths of the entry points and public files of the project in `absolute_root_dir`,
+/// sorted.
+///
+/// Entry points in `node_modules`, hidden directories, the public directory or the build output
+/// directory (`relative_output_dir`) aren't listed, nor is project metadata the config may name
+/// (e.g. `tsconfig.json`). A file in the public directory is listed once, even if an entry point
+/// has the same path.
+pub fn list_files(absolute_root_dir: &str, relative_output_dir: &str) -> Vec<String> {
+    let root_dir = Path::new(absolute_root_dir);
+
+    let mut files = Vec::new();
+    visit_public_dir(&root_dir.join(PUBLIC_DIR), "", &mut files);
+    files.extend(entry_points(root_dir, relative_output_dir));
+
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// The project's entry points (see the module docs).
+fn entry_points(root_dir: &Path, relative_output_dir: &str) -> Vec<String> {
+    let config = CONFIG_FILES
+        .iter()
+        .find_map(|file| std::fs::read_to_string(root_dir.join(file)).ok())
+        .unwrap_or_default();
+
+    let mut entry_points = string_literals(&config)
+        .into_iter()
+        .filter_map(project_file_path)
+        .filter(|path| is_entry_point(root_dir, relative_output_dir, path))
+        .collect::<Vec<_>>();
+
+    if entry_points.is_empty() && root_dir.join(DEFAULT_ENTRY_POINT).is_file() {
+        entry_points.push(DEFAULT_ENTRY_POINT.to_string());
+    }
+
+    entry_points
+}
+
+/// The contents of the string literals in JavaScript or TypeScript `source`, escapes and all.
+/// Template literals are included too.
+fn string_literals(source: &str) -> Vec<&str> {
+    let mut literals = Vec::new();
+    let mut chars = source.char_indices();
+
+    while let Some((start, quote)) = chars.next() {
+        if quote != '"' && quote != '\'' && quote != '`' {
+            continue;
+        }
+
+        let mut escaped = false;
+        for (end, c) in chars.by_ref() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                // quotes are a single byte
+                literals.push(&source[start + 1..end]);
+                break;
+            } else if c == '\n' && quote != '`' {
+                break;
+            }
+        }
+    }
+
+    literals
+}
+
+/// The path of a project file named by a literal in the config, e.g. `./app/index.html` is
+/// `app/index.html`. `None` if it isn't a relative path within the project.
+fn project_file_path(literal: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in Path::new(literal).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+
+    Some(parts.join("/"))
+}
+
+/// Whether the project file at `path` can be an entry point (see `list_files`).
+fn is_entry_point(root_dir: &Path, relative_output_dir: &str, path: &str) -> bool {
+    let output_dir = project_file_path(relative_output_dir).unwrap_or_default();
+    let is_in = |dir: &str| !dir.is_empty() && path.starts_with(&format!("{}/", dir));
+
+    let is_skipped = path
+        .split('/')
+        .any(|part| part.starts_with('.') || part == "node_modules")
+        || is_in(PUBLIC_DIR)
+        || is_in(&output_dir);
+    let is_compiler_config =
+        (path.starts_with("tsconfig") || path.starts_with("jsconfig")) && path.ends_with(".json");
+    let is_project_file =
+        PROJECT_FILES.contains(&path) || CONFIG_FILES.contains(&path) || is_compiler_config;
+
+    !is_skipped && !is_project_file && root_dir.join(path).is_file()
+}
+
+/// Adds the paths of the files in the public directory `dir` (prefixed with `prefix`) to
+/// `files`, recursively. They're all served, so they're all listed.
+fn visit_public_dir(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let path = format!("{}{}", prefix, name);
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            visit_public_dir(&entry.path(), &format!("{}/", path), files);
+        } else if file_type.is_file() || entry.path().is_file() {
+            // symlinks to files are listed, but symlinks to directories aren't followed
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Coordination of Vite's HMR port with the port the dev server was started on.
+//!
+//! The page using the assets is served by the Rust app, so Vite's HMR client opens its websocket
+//! on the app's port. That works when the app proxies it (like `vite-rs-axum-0-8`'s `ViteServe`);
+//! otherwise, `server.hmr.port` has to point it at the dev server. Since the dev server's port is
+//! only known at runtime, we start Vite with a generated config (passed via `--config`) that
+//! loads the project's own `vite.config.*` and sets `server.hmr.port` on top.
+//!
+//! `ViteServe` tells us when it proxies a project's websocket (see `set_hmr_proxied`). The config
+//! is then rewritten to leave `server.hmr.port` alone, and Vite restarts itself to apply it.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::work_dir;
+
+/// File names Vite looks for when loading its config, in the order it looks for them.
+pub(crate) const CONFIG_FILES: [&str; 6] = [
+    "vite.config.js",
+    "vite.config.mjs",
+    "vite.config.ts",
+    "vite.config.cjs",
+    "vite.config.mts",
+    "vite.config.cts",
+];
+
+static CONFIGURE_HMR: AtomicBool = AtomicBool::new(true);
+
+lazy_static::lazy_static! {
+    /// Roots of the projects whose HMR websocket is proxied by the app.
+    static ref PROXIED_ROOTS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Sets whether `server.hmr.port` is set to the port of dev servers started by vite-rs.
+///
+/// Enabled by default, except for projects whose HMR websocket is proxied (see
+/// `set_hmr_proxied`). Disable it before starting the dev server to keep your own
+/// `server.hmr.port`.
+pub fn set_configure_hmr(enabled: bool) {
+    CONFIGURE_HMR.store(enabled, Ordering::Relaxed);
+}
+
+/// Marks the HMR websocket of the project in `absolute_root_dir` as proxied by the app serving
+/// the page (`vite-rs-axum-0-8`'s `ViteServe` calls this), so `server.hmr.port` is left alone
+/// and the browser keeps connecting to the page's own origin.
+///
+/// A dev server that's already running with `server.hmr.port` set has its config rewritten,
+/// which makes Vite restart itself.
+pub fn set_hmr_proxied(absolute_root_dir: &str) {
+    let newly_proxied = PROXIED_ROOTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(absolute_root_dir.to_string());
+
+    if !newly_proxied {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(work_dir::path(absolute_root_dir)) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let is_config = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with("hmr-") && name.ends_with(".config.mjs"));
+
+        if is_config {
+            let _ = std::fs::write(entry.path(), config(absolute_root_dir, None));
+        }
+    }
+}
+
+/// Writes the config that sets `server.hmr.port` for a dev server on `port`, returning its path.
+///
+/// Returns `None` if HMR coordination is disabled (or the websocket is proxied), or the config
+/// couldn't be written, in which case Vite should load the project's config as usual.
+pub(crate) fn write_config(absolute_root_dir: &str, port: u16) -> Option<PathBuf> {
+    if !CONFIGURE_HMR.load(Ordering::Relaxed) || is_proxied(absolute_root_dir) {
+        return None;
+    }
+
+    let path = config_path(absolute_root_dir, port);
+    let written = work_dir::create(absolute_root_dir)
+        .and_then(|_| std::fs::write(&path, config(absolute_root_dir, Some(port))));
+
+    match written {
+        Ok(()) => Some(path),
+        Err(e) => {
+            println!(
+                "ERR! Could not write {}; `server.hmr.port` won't be set: {}",
+                path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Removes the config written for the dev server on `port`, if any.
+pub(crate) fn remove_config(absolute_root_dir: &str, port: u16) {
+    let _ = std::fs::remove_file(config_path(absolute_root_dir, port));
+}
+
+fn is_proxied(absolute_root_dir: &str) -> bool {
+    PROXIED_ROOTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(absolute_root_dir)
+}
+
+fn config_path(absolute_root_dir: &str, port: u16) -> PathBuf {
+    work_dir::path(absolute_root_dir).join(format!("hmr-{}.config.mjs", port))
+}
+
+/// The generated config: the project's own config, with `server.hmr.port` set to `port` if any.
+fn config(absolute_root_dir: &str, port: Option<u16>) -> String {
+    let root_dir = Path::new(absolute_root_dir);
+    let user_config = CONFIG_FILES
+        .iter()
+        .find(|file| root_dir.join(file).is_file());
+
+    // the config is in `.vite/vite-rs/`, two levels below the project's root
+    let import = match user_config {
+        // Vite bundles its config, so the project's config is bundled along with this one
+        Some(file) => format!("import userConfig from \"../../{}\";", file),
+        None => "const userConfig = {};".to_string(),
+    };
+
+    let Some(port) = port else {
+        return format!(
+            r#"// Generated by vite-rs; the app proxies the HMR websocket, so the project's config is used as-is.
+// It's removed when the dev server stops, and is safe to delete.
+{import}
+
+export default userConfig;
+"#
+        );
+    };
+
+    format!(
+        r#"// Generated by vite-rs to set `server.hmr.port` to the dev server's port.
+// It's removed when the dev server stops, and is safe to delete.
+{import}
+
+export default async (env) => {{
+  const config = {{
+    ...(typeof userConfig === "function" ? await userConfig(env) : await userConfig),
+  }};
+  const server = {{ ...config.server }};
+
+  if (server.hmr !== false) {{
+    server.hmr = {{
+      ...(typeof server.hmr === "object" ? server.hmr : {{}}),
+      port: {port},
+    }};
+  }}
+
+  return {{ ...config, server }};
+}};
+"#
+    )
+}
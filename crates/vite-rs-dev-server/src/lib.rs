@@ -9,58 +9,197 @@ pub use ctrlc;
 pub use reqwest; // exported for use in derived code
 
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-use std::sync::{Arc, Mutex};
+pub use http; // exported for use in derived code
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
 
 pub mod util;
 
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-pub struct ViteProcess(pub Arc<Mutex<GroupChild>>);
+pub mod client;
+
+mod error;
+pub use error::{DevServerError, WaitReadyError};
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod ready;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod logs;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub use logs::set_capture_logs;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod builder;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use builder::DevServerBuilder;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod config;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use config::{DevServerConfig, DEFAULT_HOST, HOST_ENV_VAR, URL_ENV_VAR};
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod port;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use port::{dev_server_port, DEFAULT_PORT_RANGE, PORT_ENV_VAR};
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod files;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use files::list_files;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod hmr;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use hmr::{set_configure_hmr, set_hmr_proxied};
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod work_dir;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod registry;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod signature;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use signature::is_vite_dev_server_running;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod shutdown;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use shutdown::{set_shutdown_grace_period, shutdown_grace_period};
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod supervisor;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use supervisor::{set_auto_restart, set_restart_on_config_change};
+
+/// RAII guard for the ViteJS dev server; the dev server is stopped when it's dropped.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub struct ViteProcess {
+    /// The current dev server process; replaced when the dev server is restarted.
+    ///
+    /// `None` when attached to a dev server for the same project that was already running,
+    /// in which case vite-rs neither restarts nor stops it.
+    pub child: Option<Arc<Mutex<GroupChild>>>,
+    /// Set once the dev server is stopped on purpose, so that it isn't restarted.
+    stopped: Arc<AtomicBool>,
+    absolute_root_dir: String,
+    host: String,
+    port: u16,
+}
 
 #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
 pub struct ViteProcess;
 
-#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-lazy_static::lazy_static! {
-    static ref VITE_PROCESS: Arc<Mutex<Option<ViteProcess>>> = Arc::new(Mutex::new(None));
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+impl ViteProcess {
+    /// No dev server runs in release builds; returns immediately.
+    pub fn wait_ready(&self, _timeout: std::time::Duration) -> Result<(), WaitReadyError> {
+        Ok(())
+    }
+
+    /// No dev server runs in release builds; returns immediately.
+    pub async fn wait_ready_async(
+        &self,
+        _timeout: std::time::Duration,
+    ) -> Result<(), WaitReadyError> {
+        Ok(())
+    }
 }
 
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-fn set_dev_server(process: ViteProcess) {
-    let original = VITE_PROCESS.lock().unwrap().replace(process);
-    if original.is_some() {
-        original
-            .unwrap()
-            .0
-            .lock()
-            .expect("(!) Could not shutdown ViteJS dev server: Mutex poisoned")
-            .kill()
-            .expect("(!) Could not shutdown ViteJS dev server.");
+impl Drop for ViteProcess {
+    fn drop(&mut self) {
+        // an attached dev server isn't ours to stop; other dev servers are stopped by their own guards
+        if let Some(child) = &self.child {
+            registry::unregister(&self.absolute_root_dir, &self.stopped);
+            registry::stop_process(child, &self.stopped, &self.absolute_root_dir, self.port);
+        }
     }
 }
 
+/// Whether vite-rs's Ctrl-C handler has been registered; it's shared by all dev servers.
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-fn unset_dev_server() {
-    let process = VITE_PROCESS.lock().unwrap().take();
-    if process.is_some() {
-        process
-            .unwrap()
-            .0
-            .lock()
-            .expect("(!) Could not shutdown ViteJS dev server: Mutex poisoned")
-            .kill()
-            .expect("(!) Could not shutdown ViteJS dev server.");
-    }
+#[cfg(feature = "ctrlc")]
+static CTRL_C_HANDLER_REGISTERED: Mutex<bool> = Mutex::new(false);
+
+/// Everything needed to spawn the ViteJS dev server, so that it can be restarted.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+struct DevServerCommand {
+    absolute_root_dir: String,
+    host: String,
+    port: u16,
+    /// Environment variables passed to Vite, e.g. from `#[env(VITE_FOO = "...")]`.
+    env: Vec<(String, String)>,
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    capture_logs: bool,
 }
 
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-impl Drop for ViteProcess {
-    fn drop(&mut self) {
-        unset_dev_server();
+impl DevServerCommand {
+    fn spawn(&self) -> Result<GroupChild, DevServerError> {
+        use command_group::CommandGroup;
+
+        // println!("Starting dev server!");
+        // start ViteJS dev server
+        #[cfg(windows)]
+        pub const NPX: &str = "npx.cmd";
+        #[cfg(not(windows))]
+        pub const NPX: &str = "npx";
+        let mut command = std::process::Command::new(NPX);
+        command
+            .arg("vite")
+            .arg("--host")
+            .arg(&self.host)
+            .arg("--port")
+            .arg(self.port.to_string())
+            .arg("--strictPort")
+            .arg("--clearScreen")
+            .arg("false")
+            // we don't want to send stdin to the dev server; this also
+            // hides the "press h + enter to show help" message that the dev server prints
+            .stdin(std::process::Stdio::null())
+            .envs(self.env.iter().map(|(key, value)| (key, value)))
+            .current_dir(&self.absolute_root_dir);
+
+        if let Some(config) = hmr::write_config(&self.absolute_root_dir, self.port) {
+            command.arg("--config").arg(config);
+        }
+
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        {
+            if self.capture_logs {
+                command
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped());
+            }
+        }
+
+        #[allow(unused_mut)]
+        let mut child = command.group_spawn().map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => DevServerError::NpxNotFound,
+            _ => DevServerError::SpawnFailed(e),
+        })?;
+
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        {
+            let child = child.inner();
+            logs::forward(child.stdout.take(), child.stderr.take());
+        }
+
+        Ok(child)
     }
 }
 
 /// Starts the ViteJS dev server.
 ///
+/// Panics if the dev server can't be started; use `try_start_dev_server` to handle the error instead.
+///
 /// Example 1 (with the included `ctrlc` feature enabled):
 ///
 /// ```ignore
@@ -89,66 +228,230 @@ impl Drop for ViteProcess {
 /// ```
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
 pub fn start_dev_server(
-    absolute_root_dir: &str,
-    host: &str,
-    port: u16,
+    config: &DevServerConfig,
     #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
 ) -> Option<ViteProcess> {
-    use command_group::CommandGroup;
+    match try_start_dev_server(
+        config,
+        #[cfg(feature = "ctrlc")]
+        register_ctrl_c_handler,
+    ) {
+        Ok(process) => Some(process),
+        Err(e) => panic!("{}", e),
+    }
+}
 
-    if !util::is_port_free(port as u16) {
-        panic!(
-            "Selected vite-rs dev server port '{}' is not available.\na) If self-selecting a port via #[dev_server_port = XXX], ensure it is free.\nb) Otherwise, remove the #[dev_server_port] attribute and let vite-rs select a free port for you at compile time.",
-            port
-        )
+/// Starts the ViteJS dev server, returning an error instead of panicking if it can't be started.
+///
+/// See `start_dev_server` for usage.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub fn try_start_dev_server(
+    config: &DevServerConfig,
+    #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+) -> Result<ViteProcess, DevServerError> {
+    start(
+        config,
+        Vec::new(),
+        #[cfg(feature = "ctrlc")]
+        register_ctrl_c_handler,
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        logs::capture_logs(),
+    )
+}
+
+/// Starts the ViteJS dev server with `env` set on top of the `#[env]` attributes.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn start(
+    config: &DevServerConfig,
+    env: Vec<(String, String)>,
+    #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+    #[cfg(any(feature = "log", feature = "tracing"))] capture_logs: bool,
+) -> Result<ViteProcess, DevServerError> {
+    // a remote dev server's lifecycle isn't ours to manage
+    if let Some(url) = config.remote_url() {
+        let url = reqwest::Url::parse(&url).map_err(|_| DevServerError::InvalidUrl(url.clone()))?;
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            return Err(DevServerError::InvalidUrl(url.to_string()));
+        };
+
+        return Ok(ViteProcess {
+            child: None,
+            stopped: Arc::new(AtomicBool::new(true)),
+            absolute_root_dir: config.absolute_root_dir.to_string(),
+            host: host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port,
+        });
     }
 
-    // println!("Starting dev server!");
-    // start ViteJS dev server
-    #[cfg(windows)]
-    pub const NPX: &'static str = "npx.cmd";
-    #[cfg(not(windows))]
-    pub const NPX: &'static str = "npx";
-    let child = Arc::new(Mutex::new(
-        std::process::Command::new(NPX)
-            .arg("vite")
-            .arg("--host")
-            .arg(host)
-            .arg("--port")
-            .arg(port.to_string())
-            .arg("--strictPort")
-            .arg("--clearScreen")
-            .arg("false")
-            // we don't want to send stdin to the dev server; this also
-            // hides the "press h + enter to show help" message that the dev server prints
-            .stdin(std::process::Stdio::null())
-            .current_dir(
-                absolute_root_dir, /*format!(
-                                       "{}/examples/basic_usage",
-                                       std::env::var("CARGO_MANIFEST_DIR").unwrap()
-                                   )*/
+    let absolute_root_dir = config.absolute_root_dir;
+    let host = config.host();
+    let configured_port = port::configured_port(config.port);
+
+    // reuse a dev server that's already running for this project (e.g. started by hand): on the
+    // set port, or where one would be found if no port is set
+    let running_port_candidates = match configured_port {
+        Some(port) => vec![port],
+        None => port::running_port_candidates(absolute_root_dir),
+    };
+    for port in running_port_candidates {
+        if !util::is_port_free(port)
+            && signature::is_vite_dev_server_running(
+                absolute_root_dir,
+                &config::connect_host(&host),
+                port,
             )
-            .group_spawn()
-            .expect("failed to start ViteJS dev server"),
-    ));
-    set_dev_server(ViteProcess(child.clone()));
+        {
+            port::set_bound_port(absolute_root_dir, port);
+            return Ok(ViteProcess {
+                child: None,
+                stopped: Arc::new(AtomicBool::new(true)),
+                absolute_root_dir: absolute_root_dir.to_string(),
+                host: config::connect_host(&host),
+                port,
+            });
+        }
+    }
+
+    let port = match configured_port {
+        Some(port) if !util::is_port_free(port) => return Err(DevServerError::PortInUse(port)),
+        Some(port) => port,
+        None => port::free_port()?,
+    };
 
     #[cfg(feature = "ctrlc")]
     {
         if register_ctrl_c_handler {
-            // We handle Ctrl-C because the node process does not exit properly otherwise
-            ctrlc::try_set_handler({
-                move || {
-                    unset_dev_server();
-                    std::process::exit(0);
-                }
-            })
-            .expect("vite-rs: Error setting Ctrl-C handler; if you are using a custom one, disable the ctrlc feature for the vite-rs crate, and follow the documentation here to integrate it: https://github.com/Wulf/vite-rs#ctrl-c-handler");
+            // We handle Ctrl-C because the node process does not exit properly otherwise.
+            // This is registered before spawning so that a conflicting handler doesn't leave
+            // an orphaned dev server behind.
+            let mut registered = CTRL_C_HANDLER_REGISTERED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+
+            if !*registered {
+                ctrlc::try_set_handler({
+                    move || {
+                        registry::stop_all();
+                        std::process::exit(0);
+                    }
+                })
+                .map_err(DevServerError::CtrlCHandler)?;
+
+                *registered = true;
+            }
         }
     }
 
+    let command = DevServerCommand {
+        absolute_root_dir: absolute_root_dir.to_string(),
+        host: host.clone(),
+        port,
+        env: config
+            .env
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .chain(env)
+            .collect(),
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        capture_logs,
+    };
+
+    let child = Arc::new(Mutex::new(command.spawn()?));
+    port::set_bound_port(absolute_root_dir, port);
+    let stopped = Arc::new(AtomicBool::new(false));
+    registry::register(registry::DevServer {
+        child: child.clone(),
+        stopped: stopped.clone(),
+        absolute_root_dir: absolute_root_dir.to_string(),
+        port,
+    });
+
+    supervisor::supervise(child.clone(), stopped.clone(), command);
+
     // We build an RAII guard around the child process so that the dev server is killed when it's dropped
-    Some(ViteProcess(child.clone()))
+    Ok(ViteProcess {
+        child: Some(child.clone()),
+        stopped,
+        absolute_root_dir: absolute_root_dir.to_string(),
+        host: config::connect_host(&host),
+        port,
+    })
+}
+
+/// Starts the ViteJS dev server without blocking the async runtime.
+///
+/// Starting the dev server spawns a process and may probe a running dev server over HTTP, so
+/// this is done on tokio's blocking thread pool. Panics if the dev server can't be started; use
+/// `try_start_dev_server_async` to handle the error instead.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(feature = "tokio")]
+pub async fn start_dev_server_async(
+    config: &DevServerConfig,
+    #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+) -> Option<ViteProcess> {
+    match try_start_dev_server_async(
+        config,
+        #[cfg(feature = "ctrlc")]
+        register_ctrl_c_handler,
+    )
+    .await
+    {
+        Ok(process) => Some(process),
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Starts the ViteJS dev server without blocking the async runtime, returning an error instead
+/// of panicking if it can't be started.
+///
+/// See `start_dev_server_async` for details.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(feature = "tokio")]
+pub async fn try_start_dev_server_async(
+    config: &DevServerConfig,
+    #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+) -> Result<ViteProcess, DevServerError> {
+    start_async(
+        config,
+        Vec::new(),
+        #[cfg(feature = "ctrlc")]
+        register_ctrl_c_handler,
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        logs::capture_logs(),
+    )
+    .await
+}
+
+/// Runs `start` on tokio's blocking thread pool.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(feature = "tokio")]
+async fn start_async(
+    config: &DevServerConfig,
+    env: Vec<(String, String)>,
+    #[cfg(feature = "ctrlc")] register_ctrl_c_handler: bool,
+    #[cfg(any(feature = "log", feature = "tracing"))] capture_logs: bool,
+) -> Result<ViteProcess, DevServerError> {
+    let config = *config;
+
+    let started = tokio::task::spawn_blocking(move || {
+        start(
+            &config,
+            env,
+            #[cfg(feature = "ctrlc")]
+            register_ctrl_c_handler,
+            #[cfg(any(feature = "log", feature = "tracing"))]
+            capture_logs,
+        )
+    })
+    .await;
+
+    match started {
+        Ok(started) => started,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
 }
 
 #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
@@ -158,12 +461,37 @@ pub fn start_dev_server(
     None
 }
 
+/// Stops every ViteJS dev server started by vite-rs; same as `stop_all_dev_servers`.
+///
+/// Use `stop_dev_server_for` (or the derived `stop_dev_server()`) to stop a single one.
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
 pub fn stop_dev_server() {
-    unset_dev_server();
+    stop_all_dev_servers();
+}
+
+/// Stops the ViteJS dev server started by vite-rs for the project in `absolute_root_dir`, if any.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub fn stop_dev_server_for(absolute_root_dir: &str) {
+    registry::stop(absolute_root_dir);
+}
+
+/// Stops every ViteJS dev server started by vite-rs (this is what the Ctrl-C handler does).
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub fn stop_all_dev_servers() {
+    registry::stop_all();
 }
 
 #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
 pub fn stop_dev_server() {
     // do nothing
 }
+
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+pub fn stop_dev_server_for(_absolute_root_dir: &str) {
+    // do nothing
+}
+
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+pub fn stop_all_dev_servers() {
+    // do nothing
+}
@@ -0,0 +1,168 @@
+//! Forwarding of the ViteJS dev server's output to the `log` or `tracing` facade.
+//!
+//! Each line Vite prints is stripped of ANSI escape codes, prefixed with `[vite]`, and emitted
+//! with the `vite` target. Vite doesn't tag its output with levels, so they're inferred: lines
+//! mentioning errors are errors, warnings (and anything else on stderr) are warnings, and the
+//! rest is info. If both features are enabled, lines are sent to `tracing`.
+//!
+//! Capturing is off by default; enable it with `set_capture_logs(true)` or per dev server with
+//! `DevServerBuilder::capture_logs`.
+
+use std::io::{BufRead, BufReader, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CAPTURE_LOGS: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the ViteJS dev server's output is forwarded to the `log`/`tracing` facade
+/// instead of being inherited by this process.
+///
+/// Disabled by default. Only affects dev servers started after the call.
+pub fn set_capture_logs(enabled: bool) {
+    CAPTURE_LOGS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether dev servers started without an explicit setting capture their output.
+pub(crate) fn capture_logs() -> bool {
+    CAPTURE_LOGS.load(Ordering::Relaxed)
+}
+
+/// Log level inferred from a line of Vite's output.
+#[derive(Clone, Copy)]
+enum Level {
+    Error,
+    Warn,
+    Info,
+}
+
+/// Which stream a line was read from.
+#[derive(Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Spawns threads that forward the dev server's piped stdout and stderr line by line.
+///
+/// The threads exit once the dev server closes its end of the pipes.
+pub(crate) fn forward(
+    stdout: Option<std::process::ChildStdout>,
+    stderr: Option<std::process::ChildStderr>,
+) {
+    if let Some(stdout) = stdout {
+        spawn_forwarder(stdout, Stream::Stdout);
+    }
+
+    if let Some(stderr) = stderr {
+        spawn_forwarder(stderr, Stream::Stderr);
+    }
+}
+
+fn spawn_forwarder<R: Read + Send + 'static>(output: R, stream: Stream) {
+    let spawned = std::thread::Builder::new()
+        .name("vite-rs-logs".to_string())
+        .spawn(move || {
+            let mut output = BufReader::new(output);
+            let mut line = Vec::new();
+
+            loop {
+                line.clear();
+                match output.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        let line = strip_ansi(&String::from_utf8_lossy(&line));
+                        let line = line.trim_end();
+
+                        if !line.trim().is_empty() {
+                            emit(infer_level(line, stream), line);
+                        }
+                    }
+                }
+            }
+        });
+
+    if let Err(e) = spawned {
+        println!("ERR! Could not forward ViteJS dev server logs: {:#?}", e);
+    }
+}
+
+fn emit(level: Level, line: &str) {
+    // Vite already prefixes some of its messages (e.g. HMR updates) with `[vite]`
+    let prefix = if line.contains("[vite]") {
+        ""
+    } else {
+        "[vite] "
+    };
+
+    #[cfg(feature = "tracing")]
+    match level {
+        Level::Error => tracing::error!(target: "vite", "{}{}", prefix, line),
+        Level::Warn => tracing::warn!(target: "vite", "{}{}", prefix, line),
+        Level::Info => tracing::info!(target: "vite", "{}{}", prefix, line),
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let level = match level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+        };
+
+        log::log!(target: "vite", level, "{}{}", prefix, line);
+    }
+}
+
+fn infer_level(line: &str, stream: Stream) -> Level {
+    let lowercase = line.to_lowercase();
+
+    if lowercase.contains("error") {
+        Level::Error
+    } else if lowercase.contains("warn") || line.trim_start().starts_with("(!)") {
+        Level::Warn
+    } else {
+        match stream {
+            Stream::Stdout => Level::Info,
+            Stream::Stderr => Level::Warn,
+        }
+    }
+}
+
+/// Removes ANSI escape sequences (colors, cursor movement, hyperlinks) from `line`.
+fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: parameters, then a final byte in '@'..='~'
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: terminated by BEL or ST (ESC \)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // two-character sequences
+            _ => {}
+        }
+    }
+
+    stripped
+}
@@ -0,0 +1,94 @@
+//! Resolution of the ViteJS dev server's port at runtime.
+//!
+//! The port is resolved in this order:
+//! 1. the port a managed dev server for the project actually bound,
+//! 2. the `VITE_RS_DEV_SERVER_PORT` environment variable,
+//! 3. the `#[dev_server_port]` attribute,
+//! 4. a free port in `DEFAULT_PORT_RANGE`, picked when the dev server is started
+//!    (or the start of that range, if no dev server was started by vite-rs).
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use crate::{util, DevServerError};
+
+/// Environment variable that overrides the ViteJS dev server's port.
+pub const PORT_ENV_VAR: &str = "VITE_RS_DEV_SERVER_PORT";
+
+/// Ports a free dev server port is picked from, unless one is configured.
+pub const DEFAULT_PORT_RANGE: Range<u16> = 21012..22022;
+
+lazy_static::lazy_static! {
+    /// Ports bound by dev servers started by vite-rs, keyed by project root.
+    static ref BOUND_PORTS: Mutex<HashMap<String, u16>> = Mutex::new(HashMap::new());
+}
+
+/// The port the ViteJS dev server for the project in `absolute_root_dir` is serving from.
+///
+/// `configured_port` is the port set with the `#[dev_server_port]` attribute, if any.
+pub fn dev_server_port(absolute_root_dir: &str, configured_port: Option<u16>) -> u16 {
+    let bound_port = BOUND_PORTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(absolute_root_dir)
+        .copied();
+
+    bound_port
+        .or_else(env_port)
+        .or(configured_port)
+        .unwrap_or(DEFAULT_PORT_RANGE.start)
+}
+
+/// The port set for the dev server, by the environment variable or else the `#[dev_server_port]`
+/// attribute (`configured_port`).
+pub(crate) fn configured_port(configured_port: Option<u16>) -> Option<u16> {
+    let env_port = match std::env::var(PORT_ENV_VAR) {
+        Ok(port) => match port.trim().parse() {
+            Ok(port) => Some(port),
+            Err(_) => {
+                println!("ERR! Ignoring invalid {} value: {:?}", PORT_ENV_VAR, port);
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    env_port.or(configured_port)
+}
+
+/// Ports a dev server for the project in `absolute_root_dir` may already be running on when no
+/// port is set: the one vite-rs last started it on, and the start of `DEFAULT_PORT_RANGE`, where
+/// `dev_server_port` looks for a dev server started by hand.
+pub(crate) fn running_port_candidates(absolute_root_dir: &str) -> Vec<u16> {
+    let bound_port = BOUND_PORTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(absolute_root_dir)
+        .copied();
+
+    let mut ports = Vec::from_iter(bound_port);
+    if !ports.contains(&DEFAULT_PORT_RANGE.start) {
+        ports.push(DEFAULT_PORT_RANGE.start);
+    }
+    ports
+}
+
+/// A free port in `DEFAULT_PORT_RANGE` to start the dev server on.
+pub(crate) fn free_port() -> Result<u16, DevServerError> {
+    util::find_free_port(DEFAULT_PORT_RANGE).ok_or(DevServerError::NoFreePort(DEFAULT_PORT_RANGE))
+}
+
+/// Records the port the dev server for the project in `absolute_root_dir` was started on.
+pub(crate) fn set_bound_port(absolute_root_dir: &str, port: u16) {
+    BOUND_PORTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(absolute_root_dir.to_string(), port);
+}
+
+fn env_port() -> Option<u16> {
+    std::env::var(PORT_ENV_VAR)
+        .ok()
+        .and_then(|port| port.trim().parse().ok())
+}
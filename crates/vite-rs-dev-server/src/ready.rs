@@ -0,0 +1,95 @@
+//! Readiness checks for a managed ViteJS dev server.
+//!
+//! The dev server is considered ready once its port accepts connections; Vite only starts
+//! listening after it has loaded its config, so this is as good as waiting for its "ready" log line.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::{ViteProcess, WaitReadyError};
+
+/// How often the dev server is polled while waiting for it to become ready.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a single connection attempt may take.
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(250);
+
+fn is_accepting_connections(host: &str, port: u16) -> bool {
+    // `localhost` may resolve to both IPv4 and IPv6 addresses; Vite may only listen on one.
+    match (host, port).to_socket_addrs() {
+        Ok(mut addrs) => {
+            addrs.any(|addr| TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_ok())
+        }
+        Err(_) => false,
+    }
+}
+
+impl ViteProcess {
+    /// Errors if the dev server process has exited.
+    fn ensure_running(&self) -> Result<(), WaitReadyError> {
+        // an attached dev server isn't our child process; only its port can be checked
+        let Some(child) = &self.child else {
+            return Ok(());
+        };
+
+        let mut child = child.lock().map_err(|_| WaitReadyError::Exited(None))?;
+
+        match child.try_wait() {
+            Ok(None) => Ok(()),
+            Ok(Some(status)) => Err(WaitReadyError::Exited(Some(status))),
+            Err(_) => Err(WaitReadyError::Exited(None)),
+        }
+    }
+
+    /// Blocks until the ViteJS dev server accepts connections.
+    ///
+    /// Errors if the dev server process exits or isn't ready within `timeout`.
+    ///
+    /// ```ignore
+    /// let guard = Assets::start_dev_server(true);
+    ///
+    /// if let Some(guard) = &guard {
+    ///     guard.wait_ready(std::time::Duration::from_secs(10)).unwrap();
+    /// }
+    /// ```
+    pub fn wait_ready(&self, timeout: Duration) -> Result<(), WaitReadyError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            self.ensure_running()?;
+
+            if is_accepting_connections(&self.host, self.port) {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(WaitReadyError::TimedOut(timeout));
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Waits until the ViteJS dev server accepts connections without blocking the async runtime.
+    ///
+    /// Errors if the dev server process exits or isn't ready within `timeout`.
+    /// Must be awaited from within a tokio runtime.
+    pub async fn wait_ready_async(&self, timeout: Duration) -> Result<(), WaitReadyError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            self.ensure_running()?;
+
+            let connect = tokio::net::TcpStream::connect((self.host.as_str(), self.port));
+            if let Ok(Ok(_)) = tokio::time::timeout(CONNECT_TIMEOUT, connect).await {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(WaitReadyError::TimedOut(timeout));
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Registry of the ViteJS dev servers started by vite-rs, keyed by project root.
+//!
+//! Each `#[derive(vite_rs::Embed)]` struct can run its own dev server (e.g. a public site and an
+//! admin app). Starting a dev server for a root replaces the one already running for it, a guard
+//! only stops its own dev server, and the Ctrl-C handler stops all of them.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use command_group::GroupChild;
+
+use crate::{hmr, shutdown};
+
+/// A dev server process started by vite-rs.
+pub(crate) struct DevServer {
+    pub(crate) child: Arc<Mutex<GroupChild>>,
+    /// Set once the dev server is stopped on purpose, so that it isn't restarted.
+    pub(crate) stopped: Arc<AtomicBool>,
+    pub(crate) absolute_root_dir: String,
+    pub(crate) port: u16,
+}
+
+impl DevServer {
+    fn stop(&self) {
+        stop_process(
+            &self.child,
+            &self.stopped,
+            &self.absolute_root_dir,
+            self.port,
+        );
+    }
+}
+
+/// Stops a dev server process and cleans up after it; harmless if it has already been stopped.
+pub(crate) fn stop_process(
+    child: &Mutex<GroupChild>,
+    stopped: &AtomicBool,
+    absolute_root_dir: &str,
+    port: u16,
+) {
+    stopped.store(true, Ordering::SeqCst);
+
+    let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+    shutdown::shutdown(&mut child);
+    hmr::remove_config(absolute_root_dir, port);
+}
+
+lazy_static::lazy_static! {
+    static ref DEV_SERVERS: Mutex<HashMap<String, DevServer>> = Mutex::new(HashMap::new());
+}
+
+/// Registers a dev server, stopping the one previously registered for the same root.
+pub(crate) fn register(server: DevServer) {
+    let previous = DEV_SERVERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(server.absolute_root_dir.clone(), server);
+
+    if let Some(previous) = previous {
+        previous.stop();
+    }
+}
+
+/// Unregisters the dev server whose `stopped` flag is `stopped`, if it's still registered
+/// (it's not once it has been replaced).
+pub(crate) fn unregister(absolute_root_dir: &str, stopped: &Arc<AtomicBool>) {
+    let mut servers = DEV_SERVERS.lock().unwrap_or_else(|e| e.into_inner());
+
+    if servers
+        .get(absolute_root_dir)
+        .is_some_and(|server| Arc::ptr_eq(&server.stopped, stopped))
+    {
+        servers.remove(absolute_root_dir);
+    }
+}
+
+/// Stops the dev server registered for `absolute_root_dir`, if any.
+pub(crate) fn stop(absolute_root_dir: &str) {
+    let server = DEV_SERVERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(absolute_root_dir);
+
+    if let Some(server) = server {
+        server.stop();
+    }
+}
+
+/// Stops every registered dev server.
+///
+/// They're stopped concurrently so that their shutdown grace periods don't add up.
+pub(crate) fn stop_all() {
+    let servers: Vec<DevServer> = DEV_SERVERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .drain()
+        .map(|(_, server)| server)
+        .collect();
+
+    std::thread::scope(|scope| {
+        for server in &servers {
+            scope.spawn(|| server.stop());
+        }
+    });
+}
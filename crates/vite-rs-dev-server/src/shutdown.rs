@@ -0,0 +1,69 @@
+//! Graceful shutdown of a managed ViteJS dev server.
+//!
+//! On unix, the dev server's process group is sent `SIGTERM` first so that Vite can clean up
+//! (temp files, esbuild workers, ...). If it's still running after the grace period, the group is
+//! sent `SIGKILL`. Either way, the group is reaped before returning.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use command_group::GroupChild;
+
+/// How long the dev server is given to exit after `SIGTERM`, unless configured otherwise.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// How often the dev server is polled while waiting for it to exit.
+#[cfg(unix)]
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+static GRACE_PERIOD_MILLIS: AtomicU64 = AtomicU64::new(DEFAULT_GRACE_PERIOD.as_millis() as u64);
+
+/// Sets how long the ViteJS dev server is given to exit after `SIGTERM` before it's killed.
+///
+/// Defaults to 3 seconds. Has no effect on Windows, where the dev server is always killed.
+pub fn set_shutdown_grace_period(grace_period: Duration) {
+    let millis = u64::try_from(grace_period.as_millis()).unwrap_or(u64::MAX);
+    GRACE_PERIOD_MILLIS.store(millis, Ordering::Relaxed);
+}
+
+/// The currently configured shutdown grace period.
+pub fn shutdown_grace_period() -> Duration {
+    Duration::from_millis(GRACE_PERIOD_MILLIS.load(Ordering::Relaxed))
+}
+
+/// Stops the dev server's process group and reaps it.
+///
+/// Failures are reported but never panic, since this runs from `Drop` and from the Ctrl-C handler.
+pub(crate) fn shutdown(child: &mut GroupChild) {
+    // `try_wait` errors once the group has already been reaped
+    if !matches!(child.try_wait(), Ok(None)) {
+        return;
+    }
+
+    #[cfg(unix)]
+    {
+        use command_group::{Signal, UnixChildExt};
+        use std::time::Instant;
+
+        if child.signal(Signal::SIGTERM).is_ok() {
+            let deadline = Instant::now() + shutdown_grace_period();
+
+            loop {
+                match child.try_wait() {
+                    Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+                    Ok(None) => break, // escalate
+                    _ => return,
+                }
+            }
+        }
+    }
+
+    if let Err(e) = child.kill() {
+        println!("ERR! Could not shutdown ViteJS dev server: {:#?}", e);
+        return;
+    }
+
+    if let Err(e) = child.wait() {
+        println!("ERR! Could not reap ViteJS dev server: {:#?}", e);
+    }
+}
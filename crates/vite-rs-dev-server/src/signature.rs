@@ -0,0 +1,94 @@
+//! Detection of a ViteJS dev server that is already running for a project.
+//!
+//! A dev server listening on the expected port may belong to another app, so the port alone
+//! doesn't tell us it's safe to reuse. Instead, we write a marker file with a unique signature
+//! to the project's `.vite/vite-rs/` directory and ask the dev server for it: Vite serves files
+//! relative to its own root, so only a dev server running for this project can return the same
+//! signature.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::work_dir;
+
+/// How long the dev server is given to serve the marker file.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Whether the ViteJS dev server listening on `host:port` is running for the project in
+/// `absolute_root_dir`.
+///
+/// Note: this briefly creates (and then removes) a `signature-*.txt` file in the project's
+/// `.vite/vite-rs/` directory, which is git-ignored.
+pub fn is_vite_dev_server_running(absolute_root_dir: &str, host: &str, port: u16) -> bool {
+    let signature = unique_signature();
+    let Some(marker) = Marker::write(absolute_root_dir, &signature) else {
+        return false;
+    };
+
+    let url = format!(
+        "http://{}:{}/{}/{}",
+        crate::config::url_host(host),
+        port,
+        work_dir::WORK_DIR,
+        marker.file_name
+    );
+
+    // The blocking client can't be used from within an async runtime, so we always
+    // send the request from a separate thread.
+    let served = std::thread::spawn(move || {
+        crate::client::blocking_client()
+            .get(url)
+            .timeout(PROBE_TIMEOUT)
+            .send()
+            .and_then(|res| res.error_for_status())
+            .and_then(|res| res.text())
+            .ok()
+    })
+    .join()
+    .ok()
+    .flatten();
+
+    drop(marker);
+
+    served.is_some_and(|served| served.trim() == signature)
+}
+
+/// The marker file, removed when dropped (even if the probe panics).
+struct Marker {
+    path: PathBuf,
+    file_name: String,
+}
+
+impl Marker {
+    fn write(absolute_root_dir: &str, signature: &str) -> Option<Marker> {
+        let file_name = format!("signature-{}.txt", signature);
+        let path = work_dir::create(absolute_root_dir).ok()?.join(&file_name);
+        std::fs::write(&path, signature).ok()?;
+
+        Some(Marker { path, file_name })
+    }
+}
+
+impl Drop for Marker {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A signature that's unique to this probe.
+fn unique_signature() -> String {
+    static PROBES: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    format!(
+        "{:x}-{:x}-{:x}",
+        std::process::id(),
+        nanos,
+        PROBES.fetch_add(1, Ordering::Relaxed)
+    )
+}
@@ -0,0 +1,159 @@
+//! Supervision of a managed ViteJS dev server.
+//!
+//! Vite can exit on its own (a syntax error in its config, running out of memory, ...), after
+//! which every request to it fails. A supervisor thread watches the dev server process, reports
+//! its exit status, and restarts it with an exponential backoff. Optionally, a change to the
+//! project's `vite.config.*` restarts it right away instead of waiting out the backoff.
+//!
+//! Vite restarts itself when its config changes while it's running, so the config is only
+//! watched once it has exited.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use command_group::GroupChild;
+
+use crate::hmr::CONFIG_FILES;
+use crate::DevServerCommand;
+
+/// How often the dev server process (and its config) is checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long to wait before the first restart; doubled after each consecutive restart.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A dev server that stayed up for this long is considered healthy, resetting the backoff.
+const STABLE_AFTER: Duration = Duration::from_secs(10);
+
+static AUTO_RESTART: AtomicBool = AtomicBool::new(true);
+static RESTART_ON_CONFIG_CHANGE: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the ViteJS dev server is restarted (with backoff) when it exits unexpectedly.
+///
+/// Enabled by default.
+pub fn set_auto_restart(enabled: bool) {
+    AUTO_RESTART.store(enabled, Ordering::Relaxed);
+}
+
+/// Sets whether a change to `vite.config.*` restarts an exited ViteJS dev server immediately.
+///
+/// Disabled by default. This works even if `set_auto_restart(false)` was called, in which case
+/// the dev server is only restarted once its config is fixed.
+pub fn set_restart_on_config_change(enabled: bool) {
+    RESTART_ON_CONFIG_CHANGE.store(enabled, Ordering::Relaxed);
+}
+
+/// Spawns a thread that restarts the dev server whenever it exits, until `stopped` is set.
+pub(crate) fn supervise(
+    child: Arc<Mutex<GroupChild>>,
+    stopped: Arc<AtomicBool>,
+    command: DevServerCommand,
+) {
+    let spawned = std::thread::Builder::new()
+        .name("vite-rs-supervisor".to_string())
+        .spawn(move || run(&child, &stopped, &command));
+
+    if let Err(e) = spawned {
+        println!("ERR! Could not supervise ViteJS dev server: {:#?}", e);
+    }
+}
+
+fn run(child: &Mutex<GroupChild>, stopped: &AtomicBool, command: &DevServerCommand) {
+    let root_dir = Path::new(&command.absolute_root_dir);
+    let mut backoff = INITIAL_BACKOFF;
+    let mut started_at = Instant::now();
+
+    loop {
+        // wait for the dev server to exit
+        let status = loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+            if stopped.load(Ordering::SeqCst) {
+                return;
+            }
+
+            match child.try_wait() {
+                Ok(None) => continue,
+                Ok(Some(status)) => break Some(status),
+                // the process was already reaped (e.g. by `wait_ready`)
+                Err(_) => break None,
+            }
+        };
+
+        if started_at.elapsed() >= STABLE_AFTER {
+            backoff = INITIAL_BACKOFF;
+        }
+
+        let auto_restart = AUTO_RESTART.load(Ordering::Relaxed);
+        let status = status.map(|s| format!(" ({})", s)).unwrap_or_default();
+        if auto_restart {
+            println!(
+                "ERR! ViteJS dev server exited{}; restarting in {:?}",
+                status, backoff
+            );
+        } else {
+            println!("ERR! ViteJS dev server exited{}", status);
+        }
+
+        let mut config_modified = config_last_modified(root_dir);
+
+        // restart once the backoff elapses or the config changes; retry if spawning fails
+        loop {
+            let restart_at = Instant::now() + backoff;
+
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+
+                if stopped.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                if RESTART_ON_CONFIG_CHANGE.load(Ordering::Relaxed) {
+                    let modified = config_last_modified(root_dir);
+                    if modified != config_modified {
+                        config_modified = modified;
+                        break;
+                    }
+                }
+
+                if AUTO_RESTART.load(Ordering::Relaxed) && Instant::now() >= restart_at {
+                    break;
+                }
+            }
+
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+
+            let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+            if stopped.load(Ordering::SeqCst) {
+                return;
+            }
+
+            match command.spawn() {
+                Ok(restarted) => {
+                    *child = restarted;
+                    started_at = Instant::now();
+                    break;
+                }
+                Err(e) => println!("ERR! Could not restart ViteJS dev server: {}", e),
+            }
+        }
+    }
+}
+
+/// Last modification times of the project's Vite config files, so that creating, deleting or
+/// editing any of them is noticed.
+fn config_last_modified(root_dir: &Path) -> Vec<Option<SystemTime>> {
+    CONFIG_FILES
+        .iter()
+        .map(|file| {
+            std::fs::metadata(root_dir.join(file))
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
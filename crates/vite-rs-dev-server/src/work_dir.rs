@@ -0,0 +1,33 @@
+//! The directory vite-rs writes its generated files to within a project: `.vite/vite-rs/`.
+//! It holds the HMR config wrappers (see `hmr`) and the marker files used to recognize a running
+//! dev server (see `signature`).
+//!
+//! Keeping them out of the project's own sources means a file left behind by a crashed (or
+//! SIGKILLed) app doesn't show up next to the user's code, and the `.gitignore` written into the
+//! directory keeps it out of version control.
+
+use std::path::{Path, PathBuf};
+
+/// Path of the directory, relative to the project's root.
+pub(crate) const WORK_DIR: &str = ".vite/vite-rs";
+
+/// The directory within the project in `absolute_root_dir`.
+pub(crate) fn path(absolute_root_dir: &str) -> PathBuf {
+    Path::new(absolute_root_dir).join(WORK_DIR)
+}
+
+/// Creates the directory (and its `.gitignore`) if needed, returning its path.
+pub(crate) fn create(absolute_root_dir: &str) -> std::io::Result<PathBuf> {
+    let dir = path(absolute_root_dir);
+    std::fs::create_dir_all(&dir)?;
+
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(
+            gitignore,
+            "# Generated by vite-rs; everything in this directory is temporary.\n*\n",
+        )?;
+    }
+
+    Ok(dir)
+}
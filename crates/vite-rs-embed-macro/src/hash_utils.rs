@@ -1,8 +1,11 @@
-use sha2::{Digest, Sha256};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use vite_rs_interface::HashAlgorithm;
 
-pub fn get_content_hash(content: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(content);
-    let hash = hasher.finalize();
-    format!("{:X}", hash)
+/// The code of `algorithm`, e.g. `vite_rs::HashAlgorithm::Blake3`.
+pub fn algorithm_code(crate_path: &syn::Path, algorithm: HashAlgorithm) -> TokenStream2 {
+    // the variants are named the same in the generated code
+    let variant = syn::Ident::new(&format!("{:?}", algorithm), proc_macro2::Span::call_site());
+
+    quote! { #crate_path::HashAlgorithm::#variant }
 }
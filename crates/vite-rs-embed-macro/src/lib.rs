@@ -1,10 +1,7 @@
 // #![feature(track_path)] // => please see comments @ crates/vite-rs/tests/recompilation_test.rs:43
 #![forbid(unsafe_code)]
 
-#[cfg(all(
-    feature = "content-hash",
-    any(feature = "debug-prod", not(debug_assertions))
-))]
+#[cfg(feature = "content-hash")]
 mod hash_utils;
 
 mod syn_utils;
@@ -52,17 +49,8 @@ fn derive_absolute_root_dir(ast: &syn::DeriveInput) -> syn::Result<String> {
     Ok(root_dir_str.to_string())
 }
 
-/// The output directory is where the compiled JS/assets are placed, relative to the `root_dir`.
-/// By default, it is set to `./dist` but can be overridden by specifying a `#[output = "./dist"]` attribute under the derive macro.
-///
-/// Moreover, any output directory specified must be within `root_dir`.
-///
-/// Since this deals with compiled assets, it shouldn't be necessary for non-release builds.
-#[cfg(any(feature = "debug-prod", not(debug_assertions)))]
-fn derive_relative_output_dir(
-    ast: &syn::DeriveInput,
-    absolute_root_dir: &str,
-) -> syn::Result<String> {
+/// The output directory as written in the `#[output]` attribute (`dist` if there's none).
+fn derive_output_attr(ast: &syn::DeriveInput) -> syn::Result<String> {
     let mut output_attrs = syn_utils::find_attribute_values(ast, "output");
     if output_attrs.len() > 1 {
         return Err(syn::Error::new_spanned(
@@ -71,11 +59,25 @@ fn derive_relative_output_dir(
         ));
     }
 
-    let mut output_dir = PathBuf::from(if output_attrs.len() == 0 {
+    Ok(if output_attrs.len() == 0 {
         "dist".to_string()
     } else {
         output_attrs.remove(0)
-    });
+    })
+}
+
+/// The output directory is where the compiled JS/assets are placed, relative to the `root_dir`.
+/// By default, it is set to `./dist` but can be overridden by specifying a `#[output = "./dist"]` attribute under the derive macro.
+///
+/// Moreover, any output directory specified must be within `root_dir`.
+///
+/// Since this deals with compiled assets, it shouldn't be necessary for non-release builds.
+#[cfg(any(feature = "debug-prod", not(debug_assertions)))]
+fn derive_relative_output_dir(
+    ast: &syn::DeriveInput,
+    absolute_root_dir: &str,
+) -> syn::Result<String> {
+    let mut output_dir = PathBuf::from(derive_output_attr(ast)?);
 
     if output_dir.is_relative() {
         let root_dir = Path::new(absolute_root_dir);
@@ -135,8 +137,13 @@ fn create_output_dir_if_not_exists(
 }
 
 /// The dev server port is the port where the vite-rs dev server will run and serve from.
-/// By default, it is set to a free port in the range 21012..22022 but can be overridden by specifying a `#[dev_server_port = "123"]` attribute under the derive macro.
-fn derive_dev_server_port(ast: &syn::DeriveInput) -> u16 {
+/// It can be set by specifying a `#[dev_server_port = "123"]` attribute under the derive macro.
+///
+/// Otherwise, the port is resolved at runtime: the `VITE_RS_DEV_SERVER_PORT` environment
+/// variable if set, or a free port in the range 21012..22022 when the dev server is started.
+/// See `vite_rs_dev_server::dev_server_port`.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn derive_dev_server_port(ast: &syn::DeriveInput) -> Option<u16> {
     let dev_server_port_attrs = syn_utils::find_attribute_values(ast, "dev_server_port");
     if dev_server_port_attrs.len() > 1 {
         panic!(
@@ -144,43 +151,210 @@ fn derive_dev_server_port(ast: &syn::DeriveInput) -> u16 {
         );
     }
 
-    let dev_server_port = dev_server_port_attrs.get(0).map(|port| {
-        let port = port
-            .parse::<u16>()
-            .expect("dev_server_port must be a valid unsigned integer (usize).");
-
-        // // We don't compile-time check if the port is free because the user has the option
-        // // of running the dev server themselves. A runtime check does exist; see the `vite-rs-dev-server` crate.
-        // if !vite_rs_dev_server::util::is_port_free(port as u16) {
-        //     panic!(
-        //         "Selected vite-rs dev server port '{}' is not available.",
-        //         port
-        //     )
-        // }
-
-        port
-    });
-
-    dev_server_port.unwrap_or_else(|| {
-        // If the user doesn't specify a dev_server_port, this function
-        // returns a free port in the range 21012..22022.
-        //
-        // This can be a source of hair-pulling because this happens on
-        // macro codegen. In other words, the selected free port may be
-        // available when the macro code is initially compiled, but may
-        // no longer be available in subsequent runs.
-        //
-        // At the same time, this is necessary because we need to
-        // coordinate the dev server's port with the generated code
-        // (to forward requests to the dev server).
-        //
-        // To save everyone's time, we'll strongly encourage users to
-        // specify a #[dev_server_port = 123].
-        vite_rs_dev_server::util::find_free_port(21012..22022)
-            .expect("Could not find a free port for the ViteJS dev server")
+    dev_server_port_attrs.first().map(|port| {
+        // We don't compile-time check if the port is free because the user has the option
+        // of running the dev server themselves. A runtime check does exist; see the `vite-rs-dev-server` crate.
+        port.parse::<u16>()
+            .expect("dev_server_port must be a valid unsigned integer (usize).")
     })
 }
 
+/// The dev server host is the host the vite-rs dev server binds to (and is requested from).
+/// By default, it is `localhost` but can be overridden by specifying a `#[dev_server_host = "0.0.0.0"]` attribute under the derive macro.
+///
+/// The `VITE_RS_DEV_SERVER_HOST` environment variable overrides it at runtime.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn derive_dev_server_host(ast: &syn::DeriveInput) -> syn::Result<Option<String>> {
+    let dev_server_host_attrs = syn_utils::find_attribute_values(ast, "dev_server_host");
+    if dev_server_host_attrs.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "When specifying a custom dev server host, #[derive(vite_rs::Embed)] must only contain a single #[dev_server_host = \"<YOUR_HOST>\"] attribute.",
+        ));
+    }
+
+    Ok(dev_server_host_attrs.into_iter().next())
+}
+
+/// The dev server URL points vite-rs at a ViteJS dev server it doesn't manage, e.g. one running
+/// in another container: `#[dev_server_url = "https://vite.example.com"]`. When set, requests are
+/// sent to this URL and `start_dev_server` doesn't start a dev server.
+///
+/// The `VITE_RS_DEV_SERVER_URL` environment variable overrides it at runtime.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn derive_dev_server_url(ast: &syn::DeriveInput) -> syn::Result<Option<String>> {
+    let dev_server_url_attrs = syn_utils::find_attribute_values(ast, "dev_server_url");
+    if dev_server_url_attrs.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "When specifying a dev server URL, #[derive(vite_rs::Embed)] must only contain a single #[dev_server_url = \"https://...\"] attribute.",
+        ));
+    }
+
+    let dev_server_url = dev_server_url_attrs.into_iter().next();
+
+    if let Some(url) = &dev_server_url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(syn::Error::new_spanned(
+                ast,
+                format!(
+                    "#[dev_server_url = \"{}\"] must be an http:// or https:// URL",
+                    url
+                ),
+            ));
+        }
+    }
+
+    Ok(dev_server_url)
+}
+
+/// The environment variable naming a ViteJS output directory to serve instead of the embedded
+/// files (see `DirectoryAssets`). By default, it's `VITE_RS_ASSETS_DIR`, but it can be changed by
+/// specifying a `#[assets_dir_env = "ADMIN_ASSETS_DIR"]` attribute under the derive macro, e.g.
+/// so that several structs can be pointed at different directories.
+///
+/// Requires the `directory` feature; it has no effect in dev builds.
+fn derive_assets_dir_env(ast: &syn::DeriveInput) -> syn::Result<Option<String>> {
+    let assets_dir_env_attrs = syn_utils::find_attribute_values(ast, "assets_dir_env");
+    if assets_dir_env_attrs.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "When specifying an assets directory environment variable, #[derive(vite_rs::Embed)] must only contain a single #[assets_dir_env = \"NAME\"] attribute.",
+        ));
+    }
+
+    if !assets_dir_env_attrs.is_empty() && !cfg!(feature = "directory") {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "#[assets_dir_env] requires the `directory` feature of vite-rs",
+        ));
+    }
+
+    Ok(assets_dir_env_attrs.into_iter().next())
+}
+
+/// The algorithm of the files' content hashes and `ETag`s (see `HashAlgorithm`). By default, it's
+/// SHA-256, but it can be changed by specifying a `#[content_hash = "blake3"]` (or `"xxh3"`)
+/// attribute under the derive macro; each algorithm requires the feature of the same name.
+///
+/// In dev builds, it's used to hash the files fetched from the ViteJS dev server.
+#[cfg(feature = "content-hash")]
+fn derive_content_hash(ast: &syn::DeriveInput) -> syn::Result<vite_rs_interface::HashAlgorithm> {
+    let content_hash_attrs = syn_utils::find_attribute_values(ast, "content_hash");
+    if content_hash_attrs.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "When specifying a content hash algorithm, #[derive(vite_rs::Embed)] must only contain a single #[content_hash = \"ALGORITHM\"] attribute.",
+        ));
+    }
+
+    let Some(name) = content_hash_attrs.into_iter().next() else {
+        return Ok(vite_rs_interface::HashAlgorithm::default());
+    };
+
+    vite_rs_interface::HashAlgorithm::from_name(&name).ok_or_else(|| {
+        let message = match name.as_str() {
+            "blake3" | "xxh3" => format!(
+                "#[content_hash = \"{0}\"] requires the `{0}` feature of vite-rs",
+                name
+            ),
+            _ => format!(
+                "#[content_hash = \"{}\"] is not supported; expected one of: sha256, blake3, xxh3",
+                name
+            ),
+        };
+
+        syn::Error::new_spanned(ast, message)
+    })
+}
+
+/// Without the `content-hash` feature, files have no content hash to choose an algorithm for.
+#[cfg(not(feature = "content-hash"))]
+fn derive_content_hash(ast: &syn::DeriveInput) -> syn::Result<()> {
+    if !syn_utils::find_attribute_values(ast, "content_hash").is_empty() {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "#[content_hash] requires the `content-hash` feature of vite-rs",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Values that can be forwarded to Vite with `#[forward_env(...)]`.
+const FORWARDABLE_ENV: [&str; 13] = [
+    "CARGO_PKG_NAME",
+    "CARGO_PKG_VERSION",
+    "CARGO_PKG_VERSION_MAJOR",
+    "CARGO_PKG_VERSION_MINOR",
+    "CARGO_PKG_VERSION_PATCH",
+    "CARGO_PKG_VERSION_PRE",
+    "CARGO_PKG_AUTHORS",
+    "CARGO_PKG_DESCRIPTION",
+    "CARGO_PKG_HOMEPAGE",
+    "CARGO_PKG_REPOSITORY",
+    "CARGO_PKG_LICENSE",
+    "CARGO_PKG_RUST_VERSION",
+    "PROFILE",
+];
+
+/// Environment variables passed to `vite build` and the dev server, e.g. for `import.meta.env`.
+/// They can be set by specifying `#[env(VITE_API_URL = "/api", VITE_FOO = "bar")]` attributes under the derive macro.
+///
+/// Values of the crate being compiled can be forwarded with `#[forward_env(CARGO_PKG_VERSION, PROFILE)]`;
+/// they're prefixed with `VITE_` (e.g. `VITE_CARGO_PKG_VERSION`) so that Vite exposes them to client code.
+/// `PROFILE` is `debug` or `release`, like the build profile.
+fn derive_env(ast: &syn::DeriveInput) -> syn::Result<Vec<(String, String)>> {
+    let mut env: Vec<(String, String)> = Vec::new();
+
+    let mut insert = |key: &syn::Ident, name: String, value: String| {
+        if env.iter().any(|(existing, _)| *existing == name) {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!("Environment variable {} is set more than once", name),
+            ));
+        }
+
+        env.push((name, value));
+        Ok(())
+    };
+
+    for (key, value) in syn_utils::find_attribute_key_values(ast, "env")? {
+        insert(&key, key.to_string(), value)?;
+    }
+
+    for key in syn_utils::find_attribute_idents(ast, "forward_env")? {
+        let name = key.to_string();
+        if !FORWARDABLE_ENV.contains(&name.as_str()) {
+            return Err(syn::Error::new_spanned(
+                &key,
+                format!(
+                    "#[forward_env] can't forward {}; expected one of: {}",
+                    name,
+                    FORWARDABLE_ENV.join(", ")
+                ),
+            ));
+        }
+
+        let value = if name == "PROFILE" {
+            // the macro is compiled with the same profile as the crate using it
+            if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            }
+            .to_string()
+        } else {
+            // cargo sets these for the crate being compiled
+            env::var(&name).unwrap_or_default()
+        };
+
+        insert(&key, format!("VITE_{}", name), value)?;
+    }
+
+    Ok(env)
+}
+
 /// If crate_path is defined, use that as a syn::Path, otherwise use the crate's name.
 /// This is useful when someone is using this crate from a crate path that is different from
 /// the default: `crate::vite_rs`. In that case, they can specify something like:
@@ -212,33 +386,78 @@ fn impl_vitejs_embed(ast: &syn::DeriveInput) -> syn::Result<TokenStream2> {
     let absolute_root_dir = derive_absolute_root_dir(ast)?;
     #[cfg(any(feature = "debug-prod", not(debug_assertions)))]
     let relative_output_dir = derive_relative_output_dir(ast, &absolute_root_dir)?;
+    // in dev builds, the output directory is only left out of `iter()`, so it needn't exist
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    let relative_output_dir = derive_output_attr(ast)?;
     let crate_path = derive_crate_path(ast)?;
 
-    let dev_server_host = "localhost";
+    // the dev server is only reached in dev builds
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    let dev_server_host = derive_dev_server_host(ast)?;
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
     let dev_server_port = derive_dev_server_port(ast);
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    let dev_server_url = derive_dev_server_url(ast)?;
+    let env = derive_env(ast)?;
+    #[cfg_attr(
+        all(debug_assertions, not(feature = "debug-prod")),
+        allow(unused_variables)
+    )]
+    let assets_dir_env = derive_assets_dir_env(ast)?;
+    #[cfg(feature = "content-hash")]
+    let hash_algorithm = derive_content_hash(ast)?;
+    // without the feature, it only rejects the attribute
+    #[cfg(not(feature = "content-hash"))]
+    derive_content_hash(ast)?;
 
     vite::build::generate_rust_code(
         /* dev-only */
         #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-        dev_server_host,
+        dev_server_host.as_deref(),
         /* dev-only */
         #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
         dev_server_port,
+        /* dev-only */
+        #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+        dev_server_url.as_deref(),
         &crate_path,
         &ast.ident,
         &absolute_root_dir,
+        &env,
+        &relative_output_dir,
         /* prod-only */
         #[cfg(any(feature = "debug-prod", not(debug_assertions)))]
-        &relative_output_dir,
+        assets_dir_env.as_deref(),
+        #[cfg(feature = "content-hash")]
+        hash_algorithm,
     )
 }
 
 /// For explanations of the attributes, please see:
 /// - #[root]: derive_absolute_root_dir (define above)
 /// - #[output]: derive_relative_output_dir (define above)
+/// - #[dev_server_host]: derive_dev_server_host (define above)
 /// - #[dev_server_port]: derive_dev_server_port (define above)
+/// - #[dev_server_url]: derive_dev_server_url (define above)
+/// - #[env], #[forward_env]: derive_env (define above)
+/// - #[assets_dir_env]: derive_assets_dir_env (define above)
+/// - #[content_hash]: derive_content_hash (define above)
 /// - #[crate_path]: derive_crate_path (define above)
-#[proc_macro_derive(Embed, attributes(root, output, dev_server_port, crate_path))]
+#[proc_macro_derive(
+    Embed,
+    attributes(
+        root,
+        output,
+        dev_server_host,
+        dev_server_port,
+        dev_server_url,
+        env,
+        forward_env,
+        assets_dir_env,
+        content_hash,
+        crate_path
+    )
+)]
 pub fn derive_input_object(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     match impl_vitejs_embed(&ast) {
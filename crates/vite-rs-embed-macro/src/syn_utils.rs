@@ -1,4 +1,4 @@
-use syn::{Data, Expr, ExprLit, Fields, Lit, Meta, MetaNameValue};
+use syn::{punctuated::Punctuated, Data, Expr, ExprLit, Fields, Lit, Meta, MetaNameValue, Token};
 
 /// Find all pairs of the `name = "value"` attribute from the derive input
 pub fn find_attribute_values(ast: &syn::DeriveInput, attr_name: &str) -> Vec<String> {
@@ -28,6 +28,58 @@ pub fn find_attribute_values(ast: &syn::DeriveInput, attr_name: &str) -> Vec<Str
         .collect()
 }
 
+/// Find all `KEY = "value"` pairs in the `name(KEY = "value", ...)` attributes from the derive input
+pub fn find_attribute_key_values(
+    ast: &syn::DeriveInput,
+    attr_name: &str,
+) -> syn::Result<Vec<(syn::Ident, String)>> {
+    let mut pairs = Vec::new();
+
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(attr_name))
+    {
+        let values =
+            attr.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+
+        for value in values {
+            let key = value.path.require_ident()?.clone();
+            match value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(val), ..
+                }) => pairs.push((key, val.value())),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        format!("#[{}] values must be string literals", attr_name),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(pairs)
+}
+
+/// Find all identifiers in the `name(IDENT, ...)` attributes from the derive input
+pub fn find_attribute_idents(
+    ast: &syn::DeriveInput,
+    attr_name: &str,
+) -> syn::Result<Vec<syn::Ident>> {
+    let mut idents = Vec::new();
+
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(attr_name))
+    {
+        idents.extend(attr.parse_args_with(Punctuated::<syn::Ident, Token![,]>::parse_terminated)?);
+    }
+
+    Ok(idents)
+}
+
 /// Returns an Err if the DeriveInput is not a unit struct
 ///
 /// # Example
@@ -0,0 +1,48 @@
+//! With the `archive` feature, the compiled files are packed into a single blob embedded with one
+//! `include_bytes!`, instead of one `include_bytes!` per file. Each file is a slice of the blob,
+//! found with the offset recorded in its entry.
+//!
+//! This keeps the generated code small for projects with many files, which compiles faster.
+
+use std::path::PathBuf;
+
+/// Name of the archive, in the output directory's `.vite` directory: it's rewritten on each build
+/// (like the manifest), and isn't listed as a compiled file.
+const ARCHIVE_FILE_NAME: &str = "vite-rs-archive.bin";
+
+pub struct Archive {
+    absolute_archive_path: String,
+    bytes: Vec<u8>,
+}
+
+impl Archive {
+    pub fn new(absolute_output_path: &str) -> Self {
+        let p = PathBuf::from_iter(&[absolute_output_path, ".vite", ARCHIVE_FILE_NAME]);
+
+        Self {
+            absolute_archive_path: p.to_str().unwrap().to_string(),
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Appends the file at `absolute_file_path` and returns its offset in the archive.
+    pub fn push(&mut self, absolute_file_path: &str) -> std::io::Result<usize> {
+        let offset = self.bytes.len();
+        self.bytes.extend(std::fs::read(absolute_file_path)?);
+
+        Ok(offset)
+    }
+
+    /// Writes the archive, and returns the code of the `ARCHIVE` const that the entries' bytes are
+    /// sliced from.
+    pub fn write(self) -> std::io::Result<proc_macro2::TokenStream> {
+        use quote::quote;
+
+        std::fs::write(&self.absolute_archive_path, &self.bytes)?;
+
+        let absolute_archive_path = &self.absolute_archive_path;
+        Ok(quote! {
+            const ARCHIVE: &'static [u8] = include_bytes!(#absolute_archive_path);
+        })
+    }
+}
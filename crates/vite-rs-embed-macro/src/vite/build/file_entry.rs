@@ -16,10 +16,18 @@ pub struct FileEntry {
 
     /// The length of the file in bytes. Useful for serving the file.
     content_length: u64,
+
+    /// The algorithm of the file's content hash.
+    #[cfg(feature = "content-hash")]
+    hash_algorithm: vite_rs_interface::HashAlgorithm,
 }
 
 impl FileEntry {
-    pub fn new(key: String, absolute_file_path: String) -> std::io::Result<Self> {
+    pub fn new(
+        key: String,
+        absolute_file_path: String,
+        #[cfg(feature = "content-hash")] hash_algorithm: vite_rs_interface::HashAlgorithm,
+    ) -> std::io::Result<Self> {
         let metadata = std::fs::metadata(&absolute_file_path)?;
         let last_modified = metadata.modified().ok().map(|last_modified| {
             let last_modified_secs = last_modified
@@ -42,6 +50,8 @@ impl FileEntry {
                 .to_string(),
             content_length: metadata.len(),
             absolute_file_path,
+            #[cfg(feature = "content-hash")]
+            hash_algorithm,
         })
     }
 
@@ -49,17 +59,43 @@ impl FileEntry {
         &self.key
     }
 
+    pub fn absolute_file_path(&self) -> &str {
+        &self.absolute_file_path
+    }
+
     pub fn match_value(&self, crate_path: &syn::Path) -> proc_macro2::TokenStream {
-        self.code(crate_path)
+        let absolute_file_path = &self.absolute_file_path;
+
+        self.code(
+            crate_path,
+            quote::quote! { include_bytes!(#absolute_file_path) },
+        )
     }
 
-    fn code(&self, crate_path: &syn::Path) -> proc_macro2::TokenStream {
-        use quote::quote;
+    /// Same as `match_value`, but the file's bytes are sliced from the `ARCHIVE` const (see
+    /// `Archive`), where they start at `offset`.
+    pub fn archived_match_value(
+        &self,
+        crate_path: &syn::Path,
+        offset: usize,
+    ) -> proc_macro2::TokenStream {
+        let content_length = self.content_length as usize;
+
+        self.code(
+            crate_path,
+            quote::quote! { ARCHIVE.split_at(#offset).1.split_at(#content_length).0 },
+        )
+    }
 
-        let absolute_file_path = &self.absolute_file_path;
+    fn code(
+        &self,
+        crate_path: &syn::Path,
+        bytes_code: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        use quote::quote;
 
         let last_modified = if let Some(last_modified) = &self.last_modified {
-            quote! { ::std::option::Option::Some(#last_modified) }
+            quote! { ::std::option::Option::Some(::std::borrow::Cow::Borrowed(#last_modified)) }
         } else {
             quote! { ::std::option::Option::None }
         };
@@ -67,28 +103,52 @@ impl FileEntry {
         let content_type = &self.content_type;
         let content_length = self.content_length;
 
-        let content_hash = if cfg!(feature = "content-hash") {
-            // We have to read the file here because it's currently not possible to use sha2 in const fns until https://github.com/RustCrypto/hashes/issues/288 is resolved.
-            // And without a const fn, we cant generate a const HASH: &'static str = "..." for each FileEntry (which would be nice and in-line with the const BYTES array).
-            // Once the above is resolved, we won't have to read the file here and in the include_bytes!.
-            let bytes =
-                std::fs::read(absolute_file_path).expect("Failed to read file to compute hash");
-            let content_hash = crate::hash_utils::get_content_hash(&bytes);
-            quote! { content_hash: #content_hash, }
-        } else {
-            quote! {}
+        // We have to read the file here because it's currently not possible to use sha2 in const fns until https://github.com/RustCrypto/hashes/issues/288 is resolved.
+        // And without a const fn, we cant generate a const HASH: &'static str = "..." for each FileEntry (which would be nice and in-line with the const BYTES array).
+        // Once the above is resolved, we won't have to read the file here and in the include_bytes!.
+        #[cfg(any(feature = "content-hash", feature = "integrity"))]
+        let bytes =
+            std::fs::read(&self.absolute_file_path).expect("Failed to read file to compute hash");
+
+        #[cfg(feature = "content-hash")]
+        let content_hash = {
+            let content_hash = self.hash_algorithm.hash(&bytes);
+            let etag = vite_rs_interface::strong_etag(&content_hash);
+
+            quote! {
+                content_hash: ::std::borrow::Cow::Borrowed(#content_hash),
+                etag: ::std::borrow::Cow::Borrowed(#etag),
+            }
+        };
+        #[cfg(not(feature = "content-hash"))]
+        let content_hash = quote! {};
+
+        #[cfg(feature = "integrity")]
+        let integrity = {
+            let integrity = vite_rs_interface::Integrity::compute(&bytes);
+            let (sha256, sha384) = (&*integrity.sha256, &*integrity.sha384);
+
+            quote! {
+                integrity: #crate_path::Integrity {
+                    sha256: ::std::borrow::Cow::Borrowed(#sha256),
+                    sha384: ::std::borrow::Cow::Borrowed(#sha384),
+                },
+            }
         };
+        #[cfg(not(feature = "integrity"))]
+        let integrity = quote! {};
 
         quote! {
             {
-                const BYTES: &'static [u8] = include_bytes!(#absolute_file_path);
+                const BYTES: &'static [u8] = #bytes_code;
 
                 #crate_path::ViteFile {
                     bytes: ::std::borrow::Cow::Borrowed(&BYTES),
                     last_modified: #last_modified,
-                    content_type: #content_type,
+                    content_type: ::std::borrow::Cow::Borrowed(#content_type),
                     content_length: #content_length,
                     #content_hash
+                    #integrity
                 }
             }
         }
@@ -0,0 +1,139 @@
+//! Generation of the perfect hash maps (`vite_rs_interface::phf::Map`) the embedded files and
+//! aliases are looked up in.
+//!
+//! Keys are hashed into buckets of about `LAMBDA` keys. The largest buckets are placed first: each
+//! bucket gets the first displacement sending all of its keys to free slots. If a bucket can't
+//! be placed, we start over with the next seed.
+//!
+//! With as many slots as keys, the last buckets have few free slots left to land on, and trying
+//! displacements for them gets quadratic in the number of keys. The `SLACK` extra slots keep
+//! most displacements free, so each bucket is placed after a few tries.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use vite_rs_interface::phf::{hash, index};
+
+/// Average number of keys per bucket.
+const LAMBDA: usize = 5;
+
+/// Extra slots, as a fraction of the number of keys.
+const SLACK: f64 = 0.2;
+
+/// Seeds tried before giving up. With `SLACK`, the first seed almost always works.
+const MAX_SEEDS: u64 = 32;
+
+struct PerfectHash {
+    seed: u64,
+    displacements: Vec<(u32, u32)>,
+    /// The index in `keys` of the key at each slot, if any.
+    slots: Vec<Option<usize>>,
+}
+
+/// The code of a `phf::Map` with the given entries (whose keys must be unique).
+pub fn map_code(
+    crate_path: &syn::Path,
+    entries: Vec<(String, TokenStream2)>,
+) -> syn::Result<TokenStream2> {
+    let keys = entries
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .collect::<Vec<_>>();
+    let PerfectHash {
+        seed,
+        displacements,
+        slots,
+    } = generate(&keys)?;
+
+    let slots = slots.into_iter().map(|key_index| match key_index {
+        Some(key_index) => {
+            let key_index = key_index as u32;
+            quote! { #key_index, }
+        }
+        None => quote! { #crate_path::phf::EMPTY, },
+    });
+    let entries = entries
+        .into_iter()
+        .map(|(key, value)| quote! { (#key, #value), });
+    let displacements = displacements
+        .into_iter()
+        .map(|(d1, d2)| quote! { (#d1, #d2), });
+
+    Ok(quote! {
+        #crate_path::phf::Map {
+            seed: #seed,
+            displacements: &[#(#displacements)*],
+            slots: &[#(#slots)*],
+            entries: &[#(#entries)*],
+        }
+    })
+}
+
+fn generate(keys: &[&str]) -> syn::Result<PerfectHash> {
+    // seeds are tried in order, so that the generated code is the same on each build
+    (0..MAX_SEEDS)
+        .find_map(|seed| try_generate(keys, seed))
+        .ok_or_else(|| {
+            syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!(
+                    "Failed to generate a perfect hash map for the {} embedded paths (tried {} seeds). Please file an issue.",
+                    keys.len(),
+                    MAX_SEEDS
+                ),
+            )
+        })
+}
+
+fn try_generate(keys: &[&str], seed: u64) -> Option<PerfectHash> {
+    let hashes = keys.iter().map(|key| hash(key, seed)).collect::<Vec<_>>();
+    let len = (keys.len() as f64 * (1.0 + SLACK)).ceil() as usize;
+
+    let buckets_len = keys.len().div_ceil(LAMBDA);
+    let mut buckets = (0..buckets_len)
+        .map(|bucket| (bucket, Vec::new()))
+        .collect::<Vec<_>>();
+    for (key_index, hashes) in hashes.iter().enumerate() {
+        buckets[(hashes.g % buckets_len as u32) as usize]
+            .1
+            .push(key_index);
+    }
+    buckets.sort_by_key(|(_, key_indices)| std::cmp::Reverse(key_indices.len()));
+
+    let mut slots = vec![None; len];
+    let mut displacements = vec![(0, 0); buckets_len];
+
+    // slots taken by the displacement being tried, marked with the number of the try
+    let mut tried = vec![0u64; len];
+    let mut try_number = 0;
+
+    'buckets: for (bucket, key_indices) in &buckets {
+        for d1 in 0..len as u32 {
+            for d2 in 0..len as u32 {
+                try_number += 1;
+
+                let fits = key_indices.iter().all(|&key_index| {
+                    let entry = index(&hashes[key_index], d1, d2, len);
+                    let is_free = slots[entry].is_none() && tried[entry] != try_number;
+                    tried[entry] = try_number;
+                    is_free
+                });
+
+                if fits {
+                    for &key_index in key_indices {
+                        slots[index(&hashes[key_index], d1, d2, len)] = Some(key_index);
+                    }
+                    displacements[*bucket] = (d1, d2);
+                    continue 'buckets;
+                }
+            }
+        }
+
+        return None;
+    }
+
+    Some(PerfectHash {
+        seed,
+        displacements,
+        slots,
+    })
+}
@@ -5,8 +5,11 @@ pub mod build {
     use quote::quote;
     use std::{collections::BTreeMap, path::PathBuf};
 
+    mod archive;
+    use archive::Archive;
     mod file_entry;
     use file_entry::FileEntry;
+    mod perfect_hash;
     mod vite_manifest;
 
     fn list_compiled_files(absolute_output_path: &str) -> Vec<String> {
@@ -31,7 +34,10 @@ pub mod build {
         crate_path: &syn::Path,
         struct_ident: &syn::Ident,
         absolute_root_dir: &str,
+        env: &[(String, String)],
         relative_output_dir: &str,
+        assets_dir_env: Option<&str>,
+        #[cfg(feature = "content-hash")] hash_algorithm: vite_rs_interface::HashAlgorithm,
     ) -> syn::Result<TokenStream2> {
         // proc_macro::tracked_path::path(absolute_root_dir); // => please see comments @ crates/vite-rs/tests/recompilation_test.rs:43
 
@@ -54,6 +60,7 @@ pub mod build {
             .arg("--manifest") // force manifest generation to `.vite/manifest.json`
             .arg("--outDir")
             .arg(&absolute_output_path)
+            .envs(env.iter().map(|(key, value)| (key, value)))
             .current_dir(absolute_root_dir)
             .spawn()
             .expect("failed to build")
@@ -85,7 +92,7 @@ pub mod build {
         let mut match_values = BTreeMap::new();
         let mut list_values = Vec::<String>::new();
 
-        list_compiled_files(&absolute_output_path)
+        let entries = list_compiled_files(&absolute_output_path)
             .iter()
             .flat_map(|relative_file_path| {
                 let absolute_file_path = {
@@ -102,16 +109,51 @@ pub mod build {
                     &relative_file_path, absolute_file_path
                 );
 
-                FileEntry::new(relative_file_path.clone(), absolute_file_path).map_err(|e| {
+                FileEntry::new(
+                    relative_file_path.clone(),
+                    absolute_file_path,
+                    #[cfg(feature = "content-hash")]
+                    hash_algorithm,
+                )
+                .map_err(|e| {
                     return syn::Error::new(
                         proc_macro2::Span::call_site(),
                         format!("Failed to read Vite manifest: {}", e),
                     );
                 })
             })
-            .for_each(|entry| {
-                match_values.insert(entry.match_key().clone(), entry.match_value(&crate_path));
-            });
+            .collect::<Vec<_>>();
+
+        // With the `archive` feature, the files are embedded in a single blob (see `Archive`).
+        let archive = if cfg!(feature = "archive") {
+            let mut archive = Archive::new(&absolute_output_path);
+
+            for entry in &entries {
+                let offset = archive.push(entry.absolute_file_path()).map_err(|e| {
+                    syn::Error::new(
+                        proc_macro2::Span::call_site(),
+                        format!("Failed to read {}: {}", entry.absolute_file_path(), e),
+                    )
+                })?;
+                match_values.insert(
+                    entry.match_key().clone(),
+                    entry.archived_match_value(crate_path, offset),
+                );
+            }
+
+            archive.write().map_err(|e| {
+                syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!("Failed to write the assets archive: {}", e),
+                )
+            })?
+        } else {
+            for entry in &entries {
+                match_values.insert(entry.match_key().clone(), entry.match_value(crate_path));
+            }
+
+            quote! {}
+        };
 
         // Aliases help us refer to entrypoints from their uncompiled name.
         //
@@ -130,42 +172,188 @@ pub mod build {
                     }
                 });
 
-            aliases.into_iter().map(|(alias, path)| {
+            let aliases = aliases
+                .into_iter()
+                .map(|(alias, path)| (alias, quote! { #path }))
+                .collect();
+
+            perfect_hash::map_code(crate_path, aliases)?
+        };
+
+        // Paths and aliases are looked up in perfect hash maps: a lookup hashes the path once.
+        let match_values = perfect_hash::map_code(crate_path, match_values.into_iter().collect())?;
+
+        let array_len = list_values.len();
+
+        // With the `directory` feature, a directory named by an environment variable is served
+        // instead of the embedded files, e.g. to deploy a new frontend without a rebuild.
+        let (
+            assets_dir,
+            get_static_from_assets_dir,
+            get_from_assets_dir,
+            try_get_from_assets_dir,
+            iter,
+        ) = if cfg!(feature = "directory") {
+            let assets_dir_env = match assets_dir_env {
+                Some(name) => quote! { #name },
+                None => quote! { #crate_path::ASSETS_DIR_ENV_VAR },
+            };
+
+            // the directory's files are hashed like the embedded ones
+            #[cfg(feature = "content-hash")]
+            let with_hash_algorithm = {
+                let algorithm = crate::hash_utils::algorithm_code(crate_path, hash_algorithm);
+                quote! { .map(|assets_dir| assets_dir.with_hash_algorithm(#algorithm)) }
+            };
+            #[cfg(not(feature = "content-hash"))]
+            let with_hash_algorithm = quote! {};
+
+            (
                 quote! {
-                    (#alias, #path),
-                }
-            })
+                    /// The ViteJS output directory named by the assets directory environment
+                    /// variable (`VITE_RS_ASSETS_DIR` by default), if it's set when the assets are
+                    /// first used. Its files are served instead of the embedded files.
+                    pub fn assets_dir() -> Option<&'static #crate_path::DirectoryAssets> {
+                        static ASSETS_DIR: ::std::sync::OnceLock<Option<#crate_path::DirectoryAssets>> =
+                            ::std::sync::OnceLock::new();
+
+                        ASSETS_DIR
+                            .get_or_init(|| {
+                                #crate_path::DirectoryAssets::from_env(#assets_dir_env)
+                                    #with_hash_algorithm
+                            })
+                            .as_ref()
+                    }
+                },
+                quote! {
+                    if Self::assets_dir().is_some() {
+                        return None;
+                    }
+                },
+                quote! {
+                    if let Some(assets_dir) = Self::assets_dir() {
+                        return #crate_path::GetFromVite::get(assets_dir, path);
+                    }
+                },
+                quote! {
+                    if let Some(assets_dir) = Self::assets_dir() {
+                        return #crate_path::GetFromVite::try_get(assets_dir, path);
+                    }
+                },
+                quote! {
+                    let names: ::std::boxed::Box<dyn ::std::iter::Iterator<Item = ::std::borrow::Cow<'static, str>>> =
+                        match Self::assets_dir() {
+                            Some(assets_dir) => #crate_path::GetFromVite::iter(assets_dir),
+                            None => ::std::boxed::Box::new(Self::names().map(|x| ::std::borrow::Cow::from(*x))),
+                        };
+
+                    names
+                },
+            )
+        } else {
+            (
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! { Self::names().map(|x| ::std::borrow::Cow::from(*x)) },
+            )
         };
 
-        let match_values = match_values.into_iter().map(|(path, bytes)| {
+        let integrity = if cfg!(feature = "integrity") {
             quote! {
-                (#path, #bytes),
+                impl #struct_ident {
+                    /// Subresource Integrity hashes of the file at `path` (or the entrypoint's
+                    /// compiled file), for the `integrity` attribute of the tag loading it.
+                    pub fn integrity(path: &str) -> Option<#crate_path::Integrity> {
+                        Self::get(path).map(|file| file.integrity)
+                    }
+                }
             }
-        });
+        } else {
+            quote! {}
+        };
 
-        let array_len = list_values.len();
+        let get_async = if cfg!(feature = "tokio") {
+            quote! {
+                impl #struct_ident {
+                    /// Same as `get`; embedded files are returned without blocking.
+                    pub async fn get_async(path: &str) -> Option<#crate_path::ViteFile> {
+                        Self::get(path)
+                    }
+
+                    /// Same as `try_get`; embedded files are returned without blocking.
+                    pub async fn try_get_async(path: &str) -> Result<#crate_path::ViteFile, #crate_path::ViteError> {
+                        Self::try_get(path)
+                    }
+
+                    pub fn boxed_async() -> ::std::boxed::Box<dyn #crate_path::GetFromViteAsync> {
+                        ::std::boxed::Box::new(#struct_ident {})
+                    }
+                }
+
+                impl #crate_path::GetFromViteAsync for #struct_ident {
+                    fn get_async<'a>(
+                        &'a self,
+                        file_path: &'a str,
+                    ) -> #crate_path::ViteFuture<'a, ::std::option::Option<#crate_path::ViteFile>> {
+                        ::std::boxed::Box::pin(#struct_ident::get_async(file_path))
+                    }
+
+                    fn try_get_async<'a>(
+                        &'a self,
+                        file_path: &'a str,
+                    ) -> #crate_path::ViteFuture<'a, ::std::result::Result<#crate_path::ViteFile, #crate_path::ViteError>> {
+                        ::std::boxed::Box::pin(#struct_ident::try_get_async(file_path))
+                    }
+
+                    fn clone_box_async(&self) -> ::std::boxed::Box<dyn #crate_path::GetFromViteAsync> {
+                        ::std::boxed::Box::new(#struct_ident {})
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
 
         Ok(quote! {
             impl #struct_ident {
                 /// Path resolution; handles aliasing for file paths
                 fn resolve(path: &str) -> &str {
-                    const ALIASES: &'static [(&'static str, &'static str)] = &[
-                        #(#aliases)*
-                    ];
+                    static ALIASES: #crate_path::phf::Map<&'static str> = #aliases;
+
+                    ALIASES.get(path).copied().unwrap_or(path)
+                }
 
-                    let path = ALIASES.binary_search_by_key(&path, |entry| entry.0).ok().map(|index| ALIASES[index].1).unwrap_or(path);
+                #assets_dir
 
-                    path
+                /// Same as `get`, but returns a reference to the embedded file instead of a copy.
+                ///
+                /// Note: files served from `assets_dir()` aren't embedded, so this returns `None`
+                /// while it's set.
+                pub fn get_static(path: &str) -> Option<&'static #crate_path::ViteFile> {
+                    #get_static_from_assets_dir
+
+                    let path = Self::resolve(path);
+
+                    #archive
+                    static ENTRIES: #crate_path::phf::Map<#crate_path::ViteFile> = #match_values;
+
+                    ENTRIES.get(path)
                 }
 
                 pub fn get(path: &str) -> Option<#crate_path::ViteFile> {
-                    let path = Self::resolve(path);
+                    #get_from_assets_dir
 
-                    const ENTRIES: &'static [(&'static str, #crate_path::ViteFile)] = &[
-                        #(#match_values)*
-                    ];
-                    let position = ENTRIES.binary_search_by_key(&path, |entry| entry.0);
-                    position.ok().map(|index| ENTRIES[index].1.clone())
+                    Self::get_static(path).cloned()
+                }
+
+                /// Same as `get`, but returns a `ViteError` instead of `None`; embedded files can
+                /// only be `NotFound`.
+                pub fn try_get(path: &str) -> Result<#crate_path::ViteFile, #crate_path::ViteError> {
+                    #try_get_from_assets_dir
+
+                    Self::get(path).ok_or(#crate_path::ViteError::NotFound)
                 }
 
                 fn names() -> ::std::slice::Iter<'static, &'static str> {
@@ -175,7 +363,7 @@ pub mod build {
 
                 /// Iterates over the file paths in the compiled ViteJS output directory
                 pub fn iter() -> impl ::std::iter::Iterator<Item = ::std::borrow::Cow<'static, str>> {
-                    Self::names().map(|x| ::std::borrow::Cow::from(*x))
+                    #iter
                 }
 
                 pub fn boxed() -> ::std::boxed::Box<dyn #crate_path::GetFromVite> {
@@ -188,10 +376,22 @@ pub mod build {
                     #struct_ident::get(file_path)
                 }
 
+                fn try_get(&self, file_path: &str) -> ::std::result::Result<#crate_path::ViteFile, #crate_path::ViteError> {
+                    #struct_ident::try_get(file_path)
+                }
+
+                fn iter(&self) -> ::std::boxed::Box<dyn ::std::iter::Iterator<Item = ::std::borrow::Cow<'static, str>>> {
+                    ::std::boxed::Box::new(#struct_ident::iter())
+                }
+
                 fn clone_box(&self) -> ::std::boxed::Box<dyn #crate_path::GetFromVite> {
                     ::std::boxed::Box::new(#struct_ident {})
                 }
             }
+
+            #get_async
+
+            #integrity
         })
     }
 }
@@ -202,45 +402,180 @@ pub mod build {
     use proc_macro2::TokenStream as TokenStream2;
     use quote::quote;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn generate_rust_code(
-        dev_server_host: &str,
-        dev_server_port: u16,
+        dev_server_host: Option<&str>,
+        dev_server_port: Option<u16>,
+        dev_server_url: Option<&str>,
         crate_path: &syn::Path,
         struct_ident: &syn::Ident,
         absolute_root_dir: &str,
+        env: &[(String, String)],
+        relative_output_dir: &str,
+        #[cfg(feature = "content-hash")] hash_algorithm: vite_rs_interface::HashAlgorithm,
     ) -> syn::Result<TokenStream2> {
-        #[cfg(feature = "ctrlc")]
+        fn option<T: quote::ToTokens>(value: Option<T>) -> TokenStream2 {
+            match value {
+                Some(value) => quote! { ::std::option::Option::Some(#value) },
+                None => quote! { ::std::option::Option::None },
+            }
+        }
+
+        // the host, port and URL are resolved at runtime; see `vite_rs_dev_server::DevServerConfig`
+        let dev_server_host = option(dev_server_host);
+        let dev_server_port = option(dev_server_port);
+        let dev_server_url = option(dev_server_url);
+        let env = env.iter().map(|(key, value)| quote! { (#key, #value), });
+        let dev_server = quote! {
+            #crate_path::vite_rs_dev_server::DevServerConfig {
+                absolute_root_dir: #absolute_root_dir,
+                host: #dev_server_host,
+                port: #dev_server_port,
+                url: #dev_server_url,
+                env: &[#(#env)*],
+            }
+        };
+
+        // `start_dev_server`'s parameters depend on the enabled features
+        let mut params = TokenStream2::new();
+        let mut args = TokenStream2::new();
+
+        if cfg!(feature = "ctrlc") {
+            params.extend(quote! { register_ctrl_c_handler: bool, });
+            args.extend(quote! { register_ctrl_c_handler, });
+        }
+
         let start_dev_server = quote! {
             pub fn start_dev_server(
-                register_ctrl_c_handler: bool,
+                #params
             ) -> Option<#crate_path::vite_rs_dev_server::ViteProcess> {
-                #crate_path::vite_rs_dev_server::start_dev_server(#absolute_root_dir, #dev_server_host, #dev_server_port, register_ctrl_c_handler)
+                #crate_path::vite_rs_dev_server::start_dev_server(&#dev_server, #args)
+            }
+
+            pub fn try_start_dev_server(
+                #params
+            ) -> Result<#crate_path::vite_rs_dev_server::ViteProcess, #crate_path::vite_rs_dev_server::DevServerError> {
+                #crate_path::vite_rs_dev_server::try_start_dev_server(&#dev_server, #args)
+            }
+
+            /// Builder for starting the ViteJS dev server with environment variables only known at runtime.
+            pub fn dev_server() -> #crate_path::vite_rs_dev_server::DevServerBuilder {
+                #crate_path::vite_rs_dev_server::DevServerBuilder::new(#dev_server)
             }
         };
 
-        #[cfg(not(feature = "ctrlc"))]
-        let start_dev_server = quote! {
-            pub fn start_dev_server() -> Option<#crate_path::vite_rs_dev_server::ViteProcess> {
-                #crate_path::vite_rs_dev_server::start_dev_server(#absolute_root_dir, #dev_server_host, #dev_server_port)
+        let start_dev_server_async = if cfg!(feature = "tokio") {
+            quote! {
+                /// Starts the ViteJS dev server without blocking the async runtime.
+                pub async fn start_dev_server_async(
+                    #params
+                ) -> Option<#crate_path::vite_rs_dev_server::ViteProcess> {
+                    #crate_path::vite_rs_dev_server::start_dev_server_async(&#dev_server, #args).await
+                }
+
+                /// Starts the ViteJS dev server without blocking the async runtime.
+                pub async fn try_start_dev_server_async(
+                    #params
+                ) -> Result<#crate_path::vite_rs_dev_server::ViteProcess, #crate_path::vite_rs_dev_server::DevServerError> {
+                    #crate_path::vite_rs_dev_server::try_start_dev_server_async(&#dev_server, #args).await
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // Files are hashed once fetched, so that they have the same strong `ETag` as in release
+        // builds.
+        #[cfg(feature = "content-hash")]
+        let content_hash = {
+            let algorithm = crate::hash_utils::algorithm_code(crate_path, hash_algorithm);
+
+            quote! {
+                let content_hash = #algorithm.hash(&bytes);
+                let etag = #crate_path::strong_etag(&content_hash);
             }
         };
+        #[cfg(not(feature = "content-hash"))]
+        let content_hash = quote! {};
 
-        let etag = if cfg!(feature = "content-hash") {
+        let content_hash_fields = if cfg!(feature = "content-hash") {
             quote! {
-                let etag = res
-                    .headers()
-                    .get(#crate_path::vite_rs_dev_server::reqwest::header::ETAG)
-                    .expect("FATAL: ViteJS dev server did not return an `ETag` header.")
-                    .to_str()
-                    .unwrap()
-                    .to_string();
+                content_hash: ::std::borrow::Cow::Owned(content_hash),
+                etag: ::std::borrow::Cow::Owned(etag),
             }
         } else {
             quote! {}
         };
 
-        let content_hash = if cfg!(feature = "content-hash") {
-            quote! { content_hash: etag, }
+        let (integrity, integrity_fn) = if cfg!(feature = "integrity") {
+            (
+                quote! { integrity: #crate_path::Integrity::compute(&bytes), },
+                quote! {
+                    impl #struct_ident {
+                        /// Subresource Integrity hashes of the file at `path`, computed from the file
+                        /// served by the ViteJS dev server.
+                        pub fn integrity(path: &str) -> Option<#crate_path::Integrity> {
+                            Self::get(path).map(|file| file.integrity)
+                        }
+                    }
+                },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
+        let get_async = if cfg!(feature = "tokio") {
+            quote! {
+                impl #struct_ident {
+                    /// Fetches a file from the ViteJS dev server without blocking the async runtime.
+                    ///
+                    /// Unlike `get`, the request is sent with the pooled async client instead of on a
+                    /// separate thread. Must be awaited from within a tokio runtime.
+                    pub async fn get_async(path: &str) -> Option<#crate_path::ViteFile> {
+                        Self::log_error(Self::try_get_async(path).await)
+                    }
+
+                    /// Same as `get_async`, but tells why the file couldn't be retrieved.
+                    pub async fn try_get_async(path: &str) -> Result<#crate_path::ViteFile, #crate_path::ViteError> {
+                        let res = #crate_path::vite_rs_dev_server::client::fetch_file(&#dev_server.base_url(), path)
+                            .await
+                            .map_err(Self::fetch_error)?;
+
+                        let bytes = res
+                            .response
+                            .bytes()
+                            .await
+                            .map_err(|e| #crate_path::ViteError::Body(e.into()))?
+                            .to_vec();
+
+                        Ok(Self::dev_file(res.content_type, bytes))
+                    }
+
+                    pub fn boxed_async() -> ::std::boxed::Box<dyn #crate_path::GetFromViteAsync> {
+                        ::std::boxed::Box::new(#struct_ident {})
+                    }
+                }
+
+                impl #crate_path::GetFromViteAsync for #struct_ident {
+                    fn get_async<'a>(
+                        &'a self,
+                        file_path: &'a str,
+                    ) -> #crate_path::ViteFuture<'a, Option<#crate_path::ViteFile>> {
+                        ::std::boxed::Box::pin(#struct_ident::get_async(file_path))
+                    }
+
+                    fn try_get_async<'a>(
+                        &'a self,
+                        file_path: &'a str,
+                    ) -> #crate_path::ViteFuture<'a, Result<#crate_path::ViteFile, #crate_path::ViteError>> {
+                        ::std::boxed::Box::pin(#struct_ident::try_get_async(file_path))
+                    }
+
+                    fn clone_box_async(&self) -> ::std::boxed::Box<dyn #crate_path::GetFromViteAsync> {
+                        ::std::boxed::Box::new(#struct_ident {})
+                    }
+                }
+            }
         } else {
             quote! {}
         };
@@ -249,71 +584,137 @@ pub mod build {
             impl #struct_ident {
                 #start_dev_server
 
+                #start_dev_server_async
+
                 pub fn stop_dev_server() {
-                    #crate_path::vite_rs_dev_server::stop_dev_server()
+                    #crate_path::vite_rs_dev_server::stop_dev_server_for(#absolute_root_dir)
+                }
+
+                /// Whether a ViteJS dev server for this struct's `#[root]` is running on its port,
+                /// e.g. one started by hand. `start_dev_server` attaches to such a dev server instead
+                /// of starting a new one. Always `false` for a remote dev server (`#[dev_server_url]`).
+                pub fn is_dev_server_running() -> bool {
+                    #dev_server.is_dev_server_running()
                 }
 
+                /// Iterates over the paths of the files a build of this struct's `#[root]` starts from:
+                /// the entry points named in the Vite config (or `index.html`), and the files in
+                /// `public/`. They can all be retrieved in release builds too. The project is scanned
+                /// on each call, so new files are picked up.
                 pub fn iter() -> impl ::std::iter::Iterator<Item = ::std::borrow::Cow<'static, str>> {
-                    // https://github.com/rust-lang/rust/issues/36375
-                    if true {
-                        unimplemented!("iter() is out of scope for dev builds and is left unimplemented. It is available in release builds (or when the `debug-prod` feature is enabled)")
-                    } else {
-                        vec![].into_iter()
-                    }
+                    #crate_path::vite_rs_dev_server::list_files(#absolute_root_dir, #relative_output_dir)
+                        .into_iter()
+                        .map(::std::borrow::Cow::Owned)
                 }
 
+                /// Returns `None` if the file doesn't exist or couldn't be fetched from the ViteJS dev
+                /// server (the latter is logged); use `try_get` to tell them apart.
                 pub fn get(path: &str) -> Option<#crate_path::ViteFile> {
+                    Self::log_error(Self::try_get(path))
+                }
+
+                /// Same as `get`, but tells why the file couldn't be retrieved.
+                pub fn try_get(path: &str) -> Result<#crate_path::ViteFile, #crate_path::ViteError> {
                     let path = path.to_string();
 
+                    // The blocking client can't be used from within an async runtime, so we always
+                    // send the request from a separate thread. Async code should use `get_async`
+                    // (with the `tokio` feature) instead; `ViteServe` goes through `proxy`.
                     std::thread::spawn(move || {
-                        let client = #crate_path::vite_rs_dev_server::reqwest::blocking::Client::new();
-                        let url = format!(
-                            "http://{}:{}/{}",
-                            #dev_server_host,
-                            #dev_server_port,
-                            path
-                        );
-
-                        match client.get(&url).send() {
-                            Ok(res) => {
-                                if res.status() == 404 {
-                                    return None;
-                                }
-
-                                let content_type = res
-                                    .headers()
-                                    .get(#crate_path::vite_rs_dev_server::reqwest::header::CONTENT_TYPE)
-                                    .expect("FATAL: ViteJS dev server did not return a content type!")
-                                    .to_str()
-                                    .unwrap()
-                                    .to_string();
-
-                                let content_length = res
-                                    .content_length()
-                                    .expect("FATAL: ViteJS dev server did not return a `Content-Length` header.");
-
-                                #etag
-
-                                let mut bytes = res.bytes().unwrap().to_vec();
-
-                                Some(#crate_path::ViteFile {
-                                    last_modified: None, /* we don't send this in dev! */
-                                    content_type: content_type,
-                                    content_length: content_length,
-                                    bytes: bytes,
-                                    #content_hash
-                                })
-                            }
-                            Err(e) => {
-                                println!("ERR! {:#?}", e);
-                                None
-                            },
-                        }
+                        let res = #crate_path::vite_rs_dev_server::client::fetch_file_blocking(&#dev_server.base_url(), &path)
+                            .map_err(Self::fetch_error)?;
+
+                        let bytes = res
+                            .response
+                            .bytes()
+                            .map_err(|e| #crate_path::ViteError::Body(e.into()))?
+                            .to_vec();
+
+                        Ok(Self::dev_file(res.content_type, bytes))
                     })
                     .join()
                     .expect("Failed to spawn thread to fetch ViteJS dev server resource.")
                 }
 
+                /// Builds the file served by the ViteJS dev server from its fetched body.
+                fn dev_file(content_type: String, bytes: Vec<u8>) -> #crate_path::ViteFile {
+                    #content_hash
+
+                    #crate_path::ViteFile {
+                        last_modified: None, /* we don't send this in dev! */
+                        content_type: ::std::borrow::Cow::Owned(content_type),
+                        content_length: bytes.len() as u64,
+                        #integrity
+                        bytes: ::std::borrow::Cow::Owned(bytes),
+                        #content_hash_fields
+                    }
+                }
+
+                /// Turns a failed fetch from the ViteJS dev server into the matching `ViteError`.
+                fn fetch_error(
+                    e: #crate_path::vite_rs_dev_server::client::FetchError,
+                ) -> #crate_path::ViteError {
+                    use #crate_path::vite_rs_dev_server::client::FetchError;
+
+                    match e {
+                        FetchError::NotFound => #crate_path::ViteError::NotFound,
+                        FetchError::Unreachable(e) => #crate_path::ViteError::DevServerUnreachable(e.into()),
+                        FetchError::Status(status) => #crate_path::ViteError::DevServerStatus(status),
+                        FetchError::MissingHeader(name) => #crate_path::ViteError::MissingHeader(name),
+                    }
+                }
+
+                /// Turns a lookup result into an `Option`, logging why the file couldn't be retrieved
+                /// unless it simply doesn't exist.
+                fn log_error<T>(result: Result<T, #crate_path::ViteError>) -> Option<T> {
+                    match result {
+                        Ok(value) => Some(value),
+                        Err(#crate_path::ViteError::NotFound) => None,
+                        Err(e) => {
+                            println!("ERR! {}", e);
+                            None
+                        }
+                    }
+                }
+
+                /// Forwards a request to the ViteJS dev server and returns its response untouched
+                /// (status, headers and streamed body). Returns `None` if the dev server is unreachable.
+                pub async fn proxy(
+                    request: #crate_path::vite_rs_dev_server::http::Request<()>,
+                ) -> Option<#crate_path::vite_rs_dev_server::http::Response<#crate_path::ViteFileBody>> {
+                    let base_url = #dev_server.base_url();
+
+                    match #crate_path::vite_rs_dev_server::client::proxy(&base_url, request).await {
+                        Ok(res) => Some(res),
+                        Err(e) => {
+                            println!("ERR! {:#?}", e);
+                            None
+                        }
+                    }
+                }
+
+                /// Forwards a protocol upgrade request (e.g. Vite's HMR websocket) to the ViteJS dev server.
+                /// Returns its response head and, if it switched protocols, the upgraded connection.
+                pub async fn proxy_upgrade(
+                    request: #crate_path::vite_rs_dev_server::http::Request<()>,
+                ) -> Option<(
+                    #crate_path::vite_rs_dev_server::http::Response<()>,
+                    Option<#crate_path::ViteUpgraded>,
+                )> {
+                    let base_url = #dev_server.base_url();
+
+                    match #crate_path::vite_rs_dev_server::client::proxy_upgrade(&base_url, request).await {
+                        Ok((head, upgraded)) => Some((
+                            head,
+                            upgraded.map(|upgraded| ::std::boxed::Box::new(upgraded) as #crate_path::ViteUpgraded),
+                        )),
+                        Err(e) => {
+                            println!("ERR! {:#?}", e);
+                            None
+                        }
+                    }
+                }
+
                 pub fn boxed() -> ::std::boxed::Box<dyn #crate_path::GetFromVite> {
                     ::std::boxed::Box::new(#struct_ident {})
                 }
@@ -324,10 +725,40 @@ pub mod build {
                     #struct_ident::get(file_path)
                 }
 
+                fn try_get(&self, file_path: &str) -> Result<#crate_path::ViteFile, #crate_path::ViteError> {
+                    #struct_ident::try_get(file_path)
+                }
+
+                fn proxy(
+                    &self,
+                    request: #crate_path::vite_rs_dev_server::http::Request<()>,
+                ) -> #crate_path::ViteFuture<'_, Option<#crate_path::vite_rs_dev_server::http::Response<#crate_path::ViteFileBody>>> {
+                    ::std::boxed::Box::pin(#struct_ident::proxy(request))
+                }
+
+                fn proxy_upgrade(
+                    &self,
+                    request: #crate_path::vite_rs_dev_server::http::Request<()>,
+                ) -> #crate_path::ViteFuture<'_, Option<(#crate_path::vite_rs_dev_server::http::Response<()>, Option<#crate_path::ViteUpgraded>)>> {
+                    ::std::boxed::Box::pin(#struct_ident::proxy_upgrade(request))
+                }
+
+                fn set_hmr_proxied(&self) {
+                    #crate_path::vite_rs_dev_server::set_hmr_proxied(#absolute_root_dir)
+                }
+
+                fn iter(&self) -> ::std::boxed::Box<dyn ::std::iter::Iterator<Item = ::std::borrow::Cow<'static, str>>> {
+                    ::std::boxed::Box::new(#struct_ident::iter())
+                }
+
                 fn clone_box(&self) -> ::std::boxed::Box<dyn #crate_path::GetFromVite> {
                     ::std::boxed::Box::new(#struct_ident {})
                 }
             }
+
+            #get_async
+
+            #integrity_fn
         })
     }
 }
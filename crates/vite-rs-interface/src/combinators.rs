@@ -0,0 +1,345 @@
+//! Combinators for serving several asset sources (e.g. several Vite projects) as one.
+//!
+//! Each one implements `GetFromVite`, so it can be boxed and handed to `ViteServe` like a derived
+//! struct. They nest, e.g. `Overlay(ThemeAssets, Prefixed("admin/", AdminAssets))`.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::{GetFromVite, ViteError, ViteFile};
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+use crate::{OnceBody, ViteFileBody, ViteFuture, ViteUpgraded};
+
+/// Serves the files of `A`, and those of `B` that `A` doesn't have.
+///
+/// Only a missing file (`ViteError::NotFound`, or a `404 Not Found` from the ViteJS dev server)
+/// falls through to `B`; any other error from `A` is returned as-is. Use this to override some
+/// files of a base project, e.g. `Overlay(CustomerTheme, BaseUi)`.
+///
+/// Note: the ViteJS dev server responds to unknown paths with its `index.html`, so in development
+/// `A` may shadow more of `B` than in release builds.
+pub struct Overlay<A, B>(pub A, pub B);
+
+/// Serves the files of `A`, or those of `B` when `A` can't serve a file for any reason (e.g. it's
+/// missing, or `A`'s dev server is unreachable).
+///
+/// If both fail, `B`'s error is returned, unless it's `ViteError::NotFound` (then `A`'s is).
+pub struct Fallback<A, B>(pub A, pub B);
+
+/// Serves the files of `A` under a path prefix, e.g. `Prefixed("admin/", AdminAssets)` serves
+/// `AdminAssets`' `index.html` as `admin/index.html` (and `admin/`). Paths outside the prefix
+/// aren't found.
+///
+/// The prefix should end with a `/`. In development, requests are proxied to `A`'s dev server
+/// with the prefix removed, so `A` sees the same paths through `proxy` as through `get`. Links
+/// in `A`'s pages should then be relative (e.g. Vite's `base: "./"`) to stay under the prefix.
+pub struct Prefixed<A>(pub &'static str, pub A);
+
+impl<A, B> GetFromVite for Overlay<A, B>
+where
+    A: GetFromVite,
+    B: GetFromVite,
+{
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        self.try_get(file_path).ok()
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        match self.0.try_get(file_path) {
+            Err(ViteError::NotFound) => self.1.try_get(file_path),
+            result => result,
+        }
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<http::Response<ViteFileBody>>> {
+        Box::pin(async move {
+            let fallthrough_request = copy_request(&request);
+
+            match self.0.proxy(request).await {
+                Some(res) if res.status() == http::StatusCode::NOT_FOUND => {
+                    match self.1.proxy(fallthrough_request).await {
+                        Some(fallthrough_res) => Some(fallthrough_res),
+                        None => Some(res),
+                    }
+                }
+                res => res,
+            }
+        })
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy_upgrade(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<(http::Response<()>, Option<ViteUpgraded>)>> {
+        Box::pin(async move {
+            let fallthrough_request = copy_request(&request);
+
+            match self.0.proxy_upgrade(request).await {
+                Some((head, _)) if head.status() == http::StatusCode::NOT_FOUND => {
+                    self.1.proxy_upgrade(fallthrough_request).await
+                }
+                res => res,
+            }
+        })
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn set_hmr_proxied(&self) {
+        self.0.set_hmr_proxied();
+        self.1.set_hmr_proxied();
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        union(self.0.iter(), self.1.iter())
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        Box::new(Overlay(self.0.clone_box(), self.1.clone_box()))
+    }
+}
+
+impl<A, B> GetFromVite for Fallback<A, B>
+where
+    A: GetFromVite,
+    B: GetFromVite,
+{
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        self.try_get(file_path).ok()
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        match self.0.try_get(file_path) {
+            Ok(file) => Ok(file),
+            Err(e) => match self.1.try_get(file_path) {
+                Err(ViteError::NotFound) => Err(e),
+                result => result,
+            },
+        }
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<http::Response<ViteFileBody>>> {
+        Box::pin(async move {
+            let fallback_request = copy_request(&request);
+
+            match self.0.proxy(request).await {
+                Some(res) if !is_error(res.status()) => Some(res),
+                res => match self.1.proxy(fallback_request).await {
+                    Some(fallback_res) => Some(fallback_res),
+                    None => res,
+                },
+            }
+        })
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy_upgrade(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<(http::Response<()>, Option<ViteUpgraded>)>> {
+        Box::pin(async move {
+            let fallback_request = copy_request(&request);
+
+            match self.0.proxy_upgrade(request).await {
+                Some((head, upgraded)) if !is_error(head.status()) => Some((head, upgraded)),
+                res => match self.1.proxy_upgrade(fallback_request).await {
+                    Some(fallback_res) => Some(fallback_res),
+                    None => res,
+                },
+            }
+        })
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn set_hmr_proxied(&self) {
+        self.0.set_hmr_proxied();
+        self.1.set_hmr_proxied();
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        union(self.0.iter(), self.1.iter())
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        Box::new(Fallback(self.0.clone_box(), self.1.clone_box()))
+    }
+}
+
+impl<A> Prefixed<A> {
+    /// The path within `A`, if `file_path` is under the prefix. The prefix itself is `A`'s
+    /// `index.html`, the way `/` is.
+    fn strip<'a>(&self, file_path: &'a str) -> Option<&'a str> {
+        match file_path.strip_prefix(self.0)? {
+            "" => Some("index.html"),
+            file_path => Some(file_path),
+        }
+    }
+
+    /// The request with the prefix removed from its path (which starts with a `/`), if its path
+    /// is under the prefix. The query is kept.
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn strip_request(&self, mut request: http::Request<()>) -> Option<http::Request<()>> {
+        let uri = request.uri();
+        let path = uri.path();
+        let file_path = self.strip(path.strip_prefix('/').unwrap_or(path))?;
+
+        let path_and_query = match uri.query() {
+            Some(query) => format!("/{}?{}", file_path, query),
+            None => format!("/{}", file_path),
+        };
+
+        let mut parts = uri.clone().into_parts();
+        parts.path_and_query = Some(path_and_query.parse().ok()?);
+        *request.uri_mut() = http::Uri::from_parts(parts).ok()?;
+        Some(request)
+    }
+}
+
+impl<A> GetFromVite for Prefixed<A>
+where
+    A: GetFromVite,
+{
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        self.try_get(file_path).ok()
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        match self.strip(file_path) {
+            Some(file_path) => self.1.try_get(file_path),
+            None => Err(ViteError::NotFound),
+        }
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<http::Response<ViteFileBody>>> {
+        if let Some(request) = self.strip_request(request) {
+            self.1.proxy(request)
+        } else {
+            Box::pin(async {
+                let mut res = http::Response::new(Box::pin(OnceBody(None)) as ViteFileBody);
+                *res.status_mut() = http::StatusCode::NOT_FOUND;
+                Some(res)
+            })
+        }
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy_upgrade(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<(http::Response<()>, Option<ViteUpgraded>)>> {
+        if let Some(request) = self.strip_request(request) {
+            self.1.proxy_upgrade(request)
+        } else {
+            Box::pin(async {
+                let mut head = http::Response::new(());
+                *head.status_mut() = http::StatusCode::NOT_FOUND;
+                Some((head, None))
+            })
+        }
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn set_hmr_proxied(&self) {
+        self.1.set_hmr_proxied();
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        let prefix = self.0;
+        Box::new(
+            self.1
+                .iter()
+                .map(move |file_path| Cow::Owned(format!("{}{}", prefix, file_path))),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        Box::new(Prefixed(self.0, self.1.clone_box()))
+    }
+}
+
+/// Lets boxed asset sources be combined, e.g. `Overlay(a.clone_box(), b.clone_box())`.
+impl GetFromVite for Box<dyn GetFromVite> {
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        (**self).get(file_path)
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        (**self).try_get(file_path)
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<http::Response<ViteFileBody>>> {
+        (**self).proxy(request)
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy_upgrade(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<(http::Response<()>, Option<ViteUpgraded>)>> {
+        (**self).proxy_upgrade(request)
+    }
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn set_hmr_proxied(&self) {
+        (**self).set_hmr_proxied();
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        (**self).iter()
+    }
+
+    fn list(&self) -> Vec<Cow<'static, str>> {
+        (**self).list()
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        (**self).clone_box()
+    }
+}
+
+/// The paths of `a`, then those of `b` that aren't in `a`.
+fn union(
+    a: Box<dyn Iterator<Item = Cow<'static, str>>>,
+    b: Box<dyn Iterator<Item = Cow<'static, str>>>,
+) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+    let mut seen = HashSet::new();
+    let paths: Vec<_> = a
+        .chain(b)
+        .filter(|path| seen.insert(path.clone()))
+        .collect();
+    Box::new(paths.into_iter())
+}
+
+/// Whether a dev server's response means it couldn't serve the request.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn is_error(status: http::StatusCode) -> bool {
+    status.is_client_error() || status.is_server_error()
+}
+
+/// Copies a request without a body, so that it can be sent to another asset source.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn copy_request(request: &http::Request<()>) -> http::Request<()> {
+    let mut copy = http::Request::new(());
+    *copy.method_mut() = request.method().clone();
+    *copy.uri_mut() = request.uri().clone();
+    *copy.version_mut() = request.version();
+    *copy.headers_mut() = request.headers().clone();
+    copy
+}
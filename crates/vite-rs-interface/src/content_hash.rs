@@ -0,0 +1,92 @@
+//! Content hashes of files, and the `ETag`s derived from them.
+
+/// Algorithm of the files' content hashes (see `ViteFile::content_hash`).
+///
+/// The derived structs use the one set with `#[content_hash = "<name>"]` (SHA-256 by default).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    /// Cryptographic like SHA-256, but several times faster. Requires the `blake3` feature.
+    #[cfg(feature = "blake3")]
+    Blake3,
+    /// XXH3 (128 bits). Not cryptographic, but the fastest; enough to tell versions of a file
+    /// apart. Requires the `xxh3` feature.
+    #[cfg(feature = "xxh3")]
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    /// The algorithm named `name` (`sha256`, `blake3` or `xxh3`), or `None` if it's unknown or its
+    /// feature isn't enabled.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(Self::Sha256),
+            #[cfg(feature = "blake3")]
+            "blake3" => Some(Self::Blake3),
+            #[cfg(feature = "xxh3")]
+            "xxh3" => Some(Self::Xxh3),
+            _ => None,
+        }
+    }
+
+    /// Hash of `bytes`, hex-encoded (uppercase).
+    pub fn hash(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Sha256 => {
+                use sha2::{Digest, Sha256};
+
+                format!("{:X}", Sha256::digest(bytes))
+            }
+            #[cfg(feature = "blake3")]
+            Self::Blake3 => blake3::hash(bytes).to_hex().to_ascii_uppercase(),
+            #[cfg(feature = "xxh3")]
+            Self::Xxh3 => format!("{:032X}", xxhash_rust::xxh3::xxh3_128(bytes)),
+        }
+    }
+}
+
+/// Strong `ETag` of a file with the given content hash: the hash in quotes, e.g. `"4F2A..."`.
+pub fn strong_etag(content_hash: &str) -> String {
+    format!("\"{}\"", content_hash)
+}
+
+/// Whether the value of an `If-None-Match` header matches `etag`.
+///
+/// The header is a list of entity-tags (or `*`), compared with the weak comparison it calls for
+/// (RFC 9110, section 13.1.2): `W/"a"` matches `"a"`.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if if_none_match.trim() == "*" {
+        return true;
+    }
+
+    let etag = opaque_tag(etag);
+    let mut rest = if_none_match;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if rest.is_empty() {
+            return false;
+        }
+
+        let tag = rest.strip_prefix("W/").unwrap_or(rest);
+        let Some(tag) = tag.strip_prefix('"') else {
+            return false; // not an entity-tag
+        };
+        let Some(end) = tag.find('"') else {
+            return false;
+        };
+
+        if Some(&tag[..end]) == etag {
+            return true;
+        }
+
+        rest = &tag[end + 1..];
+    }
+}
+
+/// The opaque tag of an entity-tag: `abc` for `"abc"` or `W/"abc"`.
+fn opaque_tag(etag: &str) -> Option<&str> {
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    etag.strip_prefix('"')?.strip_suffix('"')
+}
@@ -0,0 +1,172 @@
+//! Asset source reading a ViteJS output directory at runtime, e.g. to deploy a new `dist` without
+//! rebuilding the binary.
+
+use std::borrow::Cow;
+use std::path::{Component, Path, PathBuf};
+
+use crate::file_utils;
+use crate::{GetFromVite, ViteError, ViteFile};
+
+/// Environment variable which, when set to a ViteJS output directory, makes the derived structs
+/// serve the files in it instead of their embedded files (in release builds).
+pub const ASSETS_DIR_ENV_VAR: &str = "VITE_RS_ASSETS_DIR";
+
+/// Serves the files of a ViteJS output directory (e.g. `dist`) from disk.
+///
+/// Files are looked up the same way as in the derived structs: by their path in the output
+/// directory, or by the path of the entrypoint they were compiled from (e.g. `src/main.ts` for
+/// `assets/main-BRc6Mnhm.js`), read from the directory's `.vite/manifest.json`.
+///
+/// Nothing is cached: files (and the manifest) are read on each lookup, so the directory can be
+/// replaced while the app runs.
+#[derive(Debug, Clone)]
+pub struct DirectoryAssets {
+    dir: PathBuf,
+    #[cfg(feature = "content-hash")]
+    hash_algorithm: crate::HashAlgorithm,
+}
+
+impl DirectoryAssets {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            #[cfg(feature = "content-hash")]
+            hash_algorithm: crate::HashAlgorithm::default(),
+        }
+    }
+
+    /// Sets the algorithm of the files' content hashes (SHA-256 by default).
+    #[cfg(feature = "content-hash")]
+    pub fn with_hash_algorithm(mut self, hash_algorithm: crate::HashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// Serves the directory named by the environment variable `name` (e.g. `ASSETS_DIR_ENV_VAR`),
+    /// if it's set. Returns `None` (and prints an error) if it isn't a directory.
+    pub fn from_env(name: &str) -> Option<Self> {
+        let dir = std::env::var_os(name).filter(|dir| !dir.is_empty())?;
+
+        if !Path::new(&dir).is_dir() {
+            println!(
+                "ERR! {} is set to {:?}, which is not a directory; it's ignored.",
+                name, dir
+            );
+            return None;
+        }
+
+        Some(Self::new(dir))
+    }
+
+    /// The directory the files are read from.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file at `path` in the directory, unless the path leaves it or points into `.vite`.
+    fn file_path(&self, path: &str) -> Option<PathBuf> {
+        let path = Path::new(path);
+        let mut components = path.components();
+
+        let is_within_dir = components.all(|c| matches!(c, Component::Normal(_)));
+        let is_vite_internal = path.starts_with(".vite");
+
+        if is_within_dir && !is_vite_internal {
+            Some(self.dir.join(path))
+        } else {
+            None
+        }
+    }
+
+    /// Path resolution; handles aliasing for file paths, like the derived structs' `resolve`.
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let file_path = self.file_path(path)?;
+        if file_path.is_file() {
+            return Some(file_path);
+        }
+
+        let alias = self.manifest_entry_file(path)?;
+        self.file_path(&alias)
+    }
+
+    /// The compiled file of the manifest entrypoint `path`.
+    fn manifest_entry_file(&self, path: &str) -> Option<String> {
+        let manifest = std::fs::read(self.dir.join(".vite").join("manifest.json")).ok()?;
+        let manifest: serde_json::Value = serde_json::from_slice(&manifest).ok()?;
+        let entry = manifest.get(path)?;
+
+        if entry.get("isEntry").and_then(|v| v.as_bool()) != Some(true) {
+            return None;
+        }
+
+        entry.get("file")?.as_str().map(|file| file.to_string())
+    }
+
+    fn read(&self, file_path: &Path) -> Result<ViteFile, ViteError> {
+        let bytes = std::fs::read(file_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ViteError::NotFound,
+            _ => ViteError::Io(e),
+        })?;
+        let last_modified = std::fs::metadata(file_path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        Ok(file_utils::new_file(
+            file_path,
+            bytes,
+            last_modified,
+            #[cfg(feature = "content-hash")]
+            self.hash_algorithm,
+        ))
+    }
+
+    /// The paths of the files in `dir` (prefixed with `prefix`), recursively.
+    fn visit(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(|name| name.to_string()) else {
+                continue;
+            };
+            let path = format!("{}{}", prefix, name);
+
+            if path == ".vite" {
+                continue;
+            }
+
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                Self::visit(&entry_path, &format!("{}/", path), files);
+            } else if entry_path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+}
+
+impl GetFromVite for DirectoryAssets {
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        self.try_get(file_path).ok()
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        match self.resolve(file_path) {
+            Some(file_path) => self.read(&file_path),
+            None => Err(ViteError::NotFound),
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        let mut files = Vec::new();
+        Self::visit(&self.dir, "", &mut files);
+        files.sort();
+
+        Box::new(files.into_iter().map(Cow::Owned))
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        Box::new(self.clone())
+    }
+}
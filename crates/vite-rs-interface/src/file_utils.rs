@@ -0,0 +1,45 @@
+//! Helpers for asset sources that build their files at runtime (`DirectoryAssets`,
+//! `InMemoryAssets`) instead of having them embedded by the derive macro.
+
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::ViteFile;
+
+/// A file with the same metadata as the ones embedded by the derive macro: its content type is
+/// guessed from `path`'s extension, and its content hash is computed with `hash_algorithm`.
+pub(crate) fn new_file(
+    path: &Path,
+    bytes: Vec<u8>,
+    last_modified: Option<SystemTime>,
+    #[cfg(feature = "content-hash")] hash_algorithm: crate::HashAlgorithm,
+) -> ViteFile {
+    #[cfg(feature = "content-hash")]
+    let content_hash = hash_algorithm.hash(&bytes);
+
+    ViteFile {
+        last_modified: last_modified.and_then(format_http_date).map(Cow::Owned),
+        content_type: Cow::Owned(
+            mime_guess::from_path(path)
+                .first_or_octet_stream()
+                .to_string(),
+        ),
+        content_length: bytes.len() as u64,
+        #[cfg(feature = "content-hash")]
+        etag: Cow::Owned(crate::strong_etag(&content_hash)),
+        #[cfg(feature = "content-hash")]
+        content_hash: Cow::Owned(content_hash),
+        #[cfg(feature = "integrity")]
+        integrity: crate::Integrity::compute(&bytes),
+        bytes: Cow::Owned(bytes),
+    }
+}
+
+/// Formats a date for the `Last-Modified` header, like the dates embedded by the derive macro.
+fn format_http_date(date: SystemTime) -> Option<String> {
+    let secs = date.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_secs();
+
+    chrono::DateTime::<chrono::Utc>::from_timestamp(secs as i64, 0)
+        .map(|dt| dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+}
@@ -0,0 +1,138 @@
+//! Asset source holding files in memory, e.g. to test code serving assets without building a
+//! ViteJS project (or running Node).
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::file_utils;
+use crate::{GetFromVite, ViteError, ViteFile};
+
+/// Serves files inserted at runtime with `InMemoryAssetsBuilder`.
+///
+/// Files are served the same way in development and release builds; in development, `proxy`
+/// responds like a static file server, since there's no dev server to forward requests to.
+/// Cloning it is cheap: the files are shared.
+///
+/// ```
+/// use vite_rs_interface::{GetFromVite, InMemoryAssets};
+///
+/// let assets = InMemoryAssets::builder()
+///     .insert("index.html", "<script src=\"/assets/main-4f2a.js\"></script>")
+///     .insert("assets/main-4f2a.js", "console.log('hi')")
+///     .alias("src/main.ts", "assets/main-4f2a.js")
+///     .build();
+///
+/// let file = assets.get("src/main.ts").unwrap();
+/// assert_eq!(file.content_type, "application/javascript");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryAssets {
+    files: Arc<BTreeMap<String, ViteFile>>,
+    aliases: Arc<BTreeMap<String, String>>,
+}
+
+/// Builder for `InMemoryAssets`.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryAssetsBuilder {
+    files: BTreeMap<String, ViteFile>,
+    aliases: BTreeMap<String, String>,
+    #[cfg(feature = "content-hash")]
+    hash_algorithm: crate::HashAlgorithm,
+}
+
+impl InMemoryAssets {
+    pub fn builder() -> InMemoryAssetsBuilder {
+        InMemoryAssetsBuilder::new()
+    }
+
+    /// Path resolution; handles aliasing for file paths, like the derived structs' `resolve`.
+    fn resolve(&self, path: &str) -> Option<&ViteFile> {
+        self.files.get(path).or_else(|| {
+            let alias = self.aliases.get(path)?;
+            self.files.get(alias)
+        })
+    }
+}
+
+impl InMemoryAssetsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file at `path` (e.g. `assets/main-4f2a.js`), replacing any file already there.
+    ///
+    /// Its content type is guessed from the path's extension, its content hash is computed, and
+    /// its `Last-Modified` date is the current time.
+    pub fn insert(mut self, path: impl Into<String>, bytes: impl Into<Vec<u8>>) -> Self {
+        let path = normalize(path.into());
+        let file = file_utils::new_file(
+            Path::new(&path),
+            bytes.into(),
+            Some(SystemTime::now()),
+            #[cfg(feature = "content-hash")]
+            self.hash_algorithm,
+        );
+
+        self.files.insert(path, file);
+        self
+    }
+
+    /// Sets the algorithm of the content hashes of the files inserted next with `insert` (SHA-256
+    /// by default).
+    #[cfg(feature = "content-hash")]
+    pub fn hash_algorithm(mut self, hash_algorithm: crate::HashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// Adds a file at `path` as-is, e.g. to set a specific content type or `Last-Modified` date.
+    pub fn insert_file(mut self, path: impl Into<String>, file: ViteFile) -> Self {
+        self.files.insert(normalize(path.into()), file);
+        self
+    }
+
+    /// Makes the file at `path` also retrievable as `alias`, like the entrypoints of a ViteJS
+    /// manifest (e.g. `src/main.ts` for `assets/main-4f2a.js`). Aliases aren't listed by `iter`.
+    pub fn alias(mut self, alias: impl Into<String>, path: impl Into<String>) -> Self {
+        self.aliases
+            .insert(normalize(alias.into()), normalize(path.into()));
+        self
+    }
+
+    pub fn build(self) -> InMemoryAssets {
+        InMemoryAssets {
+            files: Arc::new(self.files),
+            aliases: Arc::new(self.aliases),
+        }
+    }
+}
+
+/// Paths are looked up without a leading `/`, like in the derived structs.
+fn normalize(path: String) -> String {
+    match path.strip_prefix('/') {
+        Some(path) => path.to_string(),
+        None => path,
+    }
+}
+
+impl GetFromVite for InMemoryAssets {
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        self.resolve(file_path).cloned()
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        self.get(file_path).ok_or(ViteError::NotFound)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        let files: Vec<_> = self.files.keys().cloned().map(Cow::Owned).collect();
+        Box::new(files.into_iter())
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,36 @@
+//! Subresource Integrity (SRI) hashes of files, for the `integrity` attribute of `<script>` and
+//! `<link>` tags: https://developer.mozilla.org/en-US/docs/Web/Security/Subresource_Integrity
+
+use std::borrow::Cow;
+
+use base64::Engine;
+use sha2::{Digest, Sha256, Sha384};
+
+/// SRI hashes of a file: the base64-encoded digest of its contents, prefixed with the algorithm.
+///
+/// Displays as `sha384`, the algorithm recommended for SRI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Integrity {
+    /// e.g. `sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=`
+    pub sha256: Cow<'static, str>,
+    /// e.g. `sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb`
+    pub sha384: Cow<'static, str>,
+}
+
+impl Integrity {
+    /// Hashes `bytes`, e.g. to check a file fetched from a CDN, or for files that aren't embedded.
+    pub fn compute(bytes: &[u8]) -> Self {
+        let base64 = base64::engine::general_purpose::STANDARD;
+
+        Self {
+            sha256: Cow::Owned(format!("sha256-{}", base64.encode(Sha256::digest(bytes)))),
+            sha384: Cow::Owned(format!("sha384-{}", base64.encode(Sha384::digest(bytes)))),
+        }
+    }
+}
+
+impl std::fmt::Display for Integrity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.sha384)
+    }
+}
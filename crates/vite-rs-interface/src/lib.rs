@@ -1,43 +1,258 @@
-// Production File
-#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+use std::borrow::Cow;
+
+mod combinators;
+pub use combinators::{Fallback, Overlay, Prefixed};
+
+#[doc(hidden)]
+pub mod phf;
+
+mod tags;
+pub use tags::{script_tag, stylesheet_tag};
+
+#[cfg(feature = "content-hash")]
+mod content_hash;
+#[cfg(feature = "content-hash")]
+pub use content_hash::{etag_matches, strong_etag, HashAlgorithm};
+
+#[cfg(feature = "integrity")]
+mod integrity;
+#[cfg(feature = "integrity")]
+pub use integrity::Integrity;
+
+#[cfg(any(feature = "directory", feature = "in-memory"))]
+mod file_utils;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod static_response;
+
+#[cfg(feature = "directory")]
+mod directory;
+#[cfg(feature = "directory")]
+pub use directory::{DirectoryAssets, ASSETS_DIR_ENV_VAR};
+
+#[cfg(feature = "in-memory")]
+mod in_memory;
+#[cfg(feature = "in-memory")]
+pub use in_memory::{InMemoryAssets, InMemoryAssetsBuilder};
+
+// File
 #[derive(Debug, Clone)]
-/// File retrieved from a ViteJS-compiled project
+/// File retrieved from a ViteJS-compiled project (release builds) or the ViteJS dev server
+/// (development builds).
+///
+/// The fields have the same types in both builds: embedded files borrow their `'static` data,
+/// while files fetched from the dev server own theirs.
 pub struct ViteFile {
-    pub bytes: ::std::borrow::Cow<'static, [u8]>,
-    pub last_modified: Option<&'static str>,
-    pub content_type: &'static str,
+    pub bytes: Cow<'static, [u8]>,
+    /// `Last-Modified` date of the file, formatted for HTTP headers.
+    ///
+    /// Note: this is always `None` in development builds.
+    pub last_modified: Option<Cow<'static, str>>,
+    pub content_type: Cow<'static, str>,
     pub content_length: u64,
     #[cfg(feature = "content-hash")]
-    /// SHA-256 hash of the file contents.
-    pub content_hash: &'static str,
+    /// Hash of the file contents (uppercase hex), with the struct's `HashAlgorithm` (SHA-256 by
+    /// default).
+    ///
+    /// Note: in development builds, it's computed from the file served by the ViteJS dev server.
+    pub content_hash: Cow<'static, str>,
+    #[cfg(feature = "content-hash")]
+    /// Strong `ETag` of the file: its content hash in quotes (see `strong_etag`).
+    ///
+    /// It's strong in development builds too, since it's computed from the bytes of the file.
+    ///
+    /// Exception: responses forwarded from the ViteJS dev server by `GetFromVite::proxy` (which
+    /// `ViteServe` uses in development) carry Vite's own weak `ETag` (`W/"..."`), not this one.
+    /// Vite answers `If-None-Match` for it, and transforms files on each request, so its `ETag`
+    /// isn't comparable with this value.
+    pub etag: Cow<'static, str>,
+    #[cfg(feature = "integrity")]
+    /// Subresource Integrity hashes of the file contents.
+    ///
+    /// Note: in development builds, they're computed from the file served by the ViteJS dev server.
+    pub integrity: Integrity,
 }
 
-// Production Struct Trait
-/// Note: this is used to allow dynamic usage of embedded asset structs.
-#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
-pub trait GetFromVite: Send + Sync + 'static {
-    fn get(&self, file_path: &str) -> Option<ViteFile>;
-    fn clone_box(&self) -> Box<dyn GetFromVite>;
+// Error
+/// Reason a file couldn't be retrieved; returned by `try_get`.
+///
+/// Embedded files (release builds) can only be `NotFound`. `Io` is returned by `DirectoryAssets`,
+/// and the other variants describe failures to fetch a file from the ViteJS dev server
+/// (development builds).
+#[derive(Debug)]
+pub enum ViteError {
+    /// There's no file at the requested path.
+    NotFound,
+    /// The ViteJS dev server couldn't be reached.
+    DevServerUnreachable(Box<dyn ::std::error::Error + Send + Sync>),
+    /// The ViteJS dev server responded with an error status (other than `404 Not Found`).
+    DevServerStatus(http::StatusCode),
+    /// The ViteJS dev server's response is missing a header needed to build the `ViteFile`.
+    MissingHeader(http::HeaderName),
+    /// The ViteJS dev server's response body couldn't be read.
+    Body(Box<dyn ::std::error::Error + Send + Sync>),
+    /// The file couldn't be read from disk.
+    Io(::std::io::Error),
+}
+
+impl ViteError {
+    /// Whether there's no file at the requested path, as opposed to the file not being retrievable.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ViteError::NotFound)
+    }
+}
+
+impl ::std::fmt::Display for ViteError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        match self {
+            ViteError::NotFound => write!(f, "File not found"),
+            ViteError::DevServerUnreachable(e) => {
+                write!(f, "Could not reach the ViteJS dev server: {}", e)
+            }
+            ViteError::DevServerStatus(status) => {
+                write!(f, "ViteJS dev server responded with {}", status)
+            }
+            ViteError::MissingHeader(header) => {
+                write!(f, "ViteJS dev server did not return a `{}` header", header)
+            }
+            ViteError::Body(e) => {
+                write!(f, "Could not read the ViteJS dev server's response: {}", e)
+            }
+            ViteError::Io(e) => write!(f, "Could not read file: {}", e),
+        }
+    }
+}
+
+impl ::std::error::Error for ViteError {
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        match self {
+            ViteError::DevServerUnreachable(e) | ViteError::Body(e) => Some(e.as_ref()),
+            ViteError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
-// Development File
+/// Boxed future returned by the async methods of `GetFromVite` and `GetFromViteAsync`.
+#[cfg(any(all(debug_assertions, not(feature = "debug-prod")), feature = "tokio"))]
+pub type ViteFuture<'a, T> =
+    ::std::pin::Pin<Box<dyn ::std::future::Future<Output = T> + Send + 'a>>;
+
+/// Body of a file streamed from the ViteJS dev server, chunk by chunk.
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-#[derive(Debug, Clone)]
-/// File retreived from the ViteJS dev server
-pub struct ViteFile {
-    pub bytes: Vec<u8>,
-    pub last_modified: Option<String>,
-    pub content_type: String,
-    pub content_length: u64,
-    #[cfg(feature = "content-hash")]
-    /// Note: in development mode, this is a weak hash returned by the ViteJS dev server.
-    pub content_hash: String,
+pub type ViteFileBody = ::std::pin::Pin<
+    Box<
+        dyn futures_core::Stream<
+                Item = Result<bytes::Bytes, Box<dyn ::std::error::Error + Send + Sync>>,
+            > + Send,
+    >,
+>;
+
+/// Body made of a single chunk (or none), for responses that aren't streamed from the dev server.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub(crate) struct OnceBody(pub(crate) Option<bytes::Bytes>);
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+impl futures_core::Stream for OnceBody {
+    type Item = Result<bytes::Bytes, Box<dyn ::std::error::Error + Send + Sync>>;
+
+    fn poll_next(
+        mut self: ::std::pin::Pin<&mut Self>,
+        _cx: &mut ::std::task::Context<'_>,
+    ) -> ::std::task::Poll<Option<Self::Item>> {
+        ::std::task::Poll::Ready(self.0.take().map(Ok))
+    }
 }
 
-// Development Struct Trait
-/// Note: this is used to allow dynamic usage of embedded asset structs.
+/// IO of a connection to the ViteJS dev server after a protocol upgrade.
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub trait ViteUpgradedIo:
+    tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static
+{
+}
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+impl<T> ViteUpgradedIo for T where
+    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static
+{
+}
+
+/// Connection to the ViteJS dev server after a protocol upgrade (e.g. Vite's HMR websocket).
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub type ViteUpgraded = Box<dyn ViteUpgradedIo>;
+
+// Struct Trait
+/// Note: this is used to allow dynamic usage of embedded asset structs.
+///
+/// The required methods are the same in every build. In development builds, `proxy` and
+/// `proxy_upgrade` are added: the derived structs forward requests to the ViteJS dev server, and
+/// the default implementations respond like a static file server with the files from `try_get`.
 pub trait GetFromVite: Send + Sync + 'static {
     fn get(&self, file_path: &str) -> Option<ViteFile>;
+    /// Same as `get`, but tells why the file couldn't be retrieved, e.g. whether it doesn't exist
+    /// or (in development builds) the dev server is unreachable or responded with an error.
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError>;
+    /// Forwards a request to the ViteJS dev server and returns its response untouched.
+    ///
+    /// The request's method, URI and headers (e.g. `If-None-Match`) are passed through, and the
+    /// response's status, headers (e.g. `Cache-Control`, `Content-Encoding`) and streamed body
+    /// are returned as-is, minus hop-by-hop headers. Returns `None` if the dev server is unreachable.
+    /// This includes Vite's weak `ETag`, which differs from `ViteFile::etag` (see there).
+    ///
+    /// By default, responds with the file from `try_get` (`/` being `index.html`), with its strong
+    /// `ETag`.
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy(
+        &self,
+        request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<http::Response<ViteFileBody>>> {
+        let file = self.try_get(static_response::request_file_path(&request));
+        let res = static_response::file_response(&request, file);
+        Box::pin(async move { Some(res) })
+    }
+    /// Forwards a protocol upgrade request (e.g. Vite's HMR websocket) to the ViteJS dev server.
+    ///
+    /// Returns the dev server's response head and, if it switched protocols, the upgraded
+    /// connection to tunnel the client's connection through. Returns `None` if the dev server
+    /// is unreachable.
+    ///
+    /// By default, there's no HMR websocket to connect to, so it responds with `404 Not Found`.
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn proxy_upgrade(
+        &self,
+        _request: http::Request<()>,
+    ) -> ViteFuture<'_, Option<(http::Response<()>, Option<ViteUpgraded>)>> {
+        Box::pin(async { Some(static_response::upgrade_not_found()) })
+    }
+    /// Tells the asset source that the app proxies the HMR websocket of its ViteJS dev server
+    /// (`ViteServe` calls this), so that the browser can connect to it on the page's own origin.
+    ///
+    /// The derived structs stop pointing Vite's HMR client at the dev server's port. Does nothing
+    /// by default.
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    fn set_hmr_proxied(&self) {}
+    /// Iterates over the paths of the files that can be retrieved with `get`.
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>>;
+    /// Lists the paths of the files that can be retrieved with `get`; see `iter`.
+    fn list(&self) -> Vec<Cow<'static, str>> {
+        self.iter().collect()
+    }
     fn clone_box(&self) -> Box<dyn GetFromVite>;
 }
+
+// Async Struct Trait
+/// Async variant of `GetFromVite`, for fetching files without blocking the async runtime.
+///
+/// In development, files are fetched from the ViteJS dev server with a pooled async client
+/// (instead of a blocking request on a separate thread), so this must be polled from within a
+/// tokio runtime. In release builds, files are embedded and returned immediately.
+#[cfg(feature = "tokio")]
+pub trait GetFromViteAsync: Send + Sync + 'static {
+    fn get_async<'a>(&'a self, file_path: &'a str) -> ViteFuture<'a, Option<ViteFile>>;
+    /// Same as `get_async`, but tells why the file couldn't be retrieved.
+    fn try_get_async<'a>(
+        &'a self,
+        file_path: &'a str,
+    ) -> ViteFuture<'a, Result<ViteFile, ViteError>>;
+    fn clone_box_async(&self) -> Box<dyn GetFromViteAsync>;
+}
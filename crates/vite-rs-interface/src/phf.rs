@@ -0,0 +1,93 @@
+//! Perfect hash maps, generated by the derive macro for the embedded files' paths and aliases.
+//!
+//! The keys are known at compile time, so the macro picks a seed and a displacement per bucket
+//! (see "Hash, displace, and compress", Belazzougui et al.) that send each key to its own slot.
+//! There are a few more slots than keys, which keeps the search for displacements fast. A lookup
+//! hashes the path once and compares it to a single entry, without allocating.
+//!
+//! Not part of the public API: the hashing has to match the one the macro generated the map with,
+//! which is why both use the functions here.
+
+/// Marks a slot without an entry.
+pub const EMPTY: u32 = u32::MAX;
+
+/// A map from paths to `V`, generated by the derive macro.
+pub struct Map<V: 'static> {
+    pub seed: u64,
+    /// One `(d1, d2)` displacement per bucket.
+    pub displacements: &'static [(u32, u32)],
+    /// For each slot a key's hash can be displaced to, the index of its entry (or `EMPTY`).
+    pub slots: &'static [u32],
+    pub entries: &'static [(&'static str, V)],
+}
+
+impl<V: 'static> Map<V> {
+    pub fn get(&self, key: &str) -> Option<&'static V> {
+        if self.displacements.is_empty() {
+            return None;
+        }
+
+        let hashes = hash(key, self.seed);
+        let (d1, d2) = self.displacements[(hashes.g % self.displacements.len() as u32) as usize];
+        let entry = self.slots[index(&hashes, d1, d2, self.slots.len())];
+        if entry == EMPTY {
+            return None;
+        }
+
+        let (entry_key, value) = &self.entries[entry as usize];
+
+        if *entry_key == key {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// The hashes of a key: `g` picks its bucket, `f1` and `f2` its entry (once displaced).
+pub struct Hashes {
+    pub g: u32,
+    pub f1: u32,
+    pub f2: u32,
+}
+
+/// FNV-1a, seeded and finalized with SplitMix64's mixer so that every bit of the seed matters.
+pub fn hash(key: &str, seed: u64) -> Hashes {
+    let mut h = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for byte in key.bytes() {
+        h ^= byte as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    let h1 = mix(h);
+    let h2 = mix(h1 ^ seed);
+
+    Hashes {
+        g: (h1 >> 32) as u32,
+        f1: h1 as u32,
+        f2: h2 as u32,
+    }
+}
+
+/// The index of the slot for `hashes`, given its bucket's displacement.
+pub fn index(hashes: &Hashes, d1: u32, d2: u32, len: usize) -> usize {
+    let displaced = d2
+        .wrapping_add(hashes.f1.wrapping_mul(d1))
+        .wrapping_add(hashes.f2);
+
+    (displaced % len as u32) as usize
+}
+
+fn mix(mut h: u64) -> u64 {
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
@@ -0,0 +1,67 @@
+//! Default responses of `GetFromVite::proxy` and `GetFromVite::proxy_upgrade`, for asset sources
+//! without a ViteJS dev server to forward requests to (e.g. `InMemoryAssets`).
+
+use crate::{OnceBody, ViteError, ViteFile, ViteFileBody, ViteUpgraded};
+
+/// The file path requested by a request; `/` is `index.html`, like with Vite.
+pub(crate) fn request_file_path(request: &http::Request<()>) -> &str {
+    match request.uri().path().trim_start_matches('/') {
+        "" => "index.html",
+        path => path,
+    }
+}
+
+/// Responds to a request like a static file server would: with the file's strong `ETag`, or
+/// `304 Not Modified` if the request's `If-None-Match` matches it.
+pub(crate) fn file_response(
+    request: &http::Request<()>,
+    file: Result<ViteFile, ViteError>,
+) -> http::Response<ViteFileBody> {
+    let mut res = http::Response::new(Box::pin(OnceBody(None)) as ViteFileBody);
+
+    match file {
+        Ok(file) => {
+            let headers = res.headers_mut();
+            if let Ok(value) = http::HeaderValue::from_str(&file.content_type) {
+                headers.insert(http::header::CONTENT_TYPE, value);
+            }
+            headers.insert(http::header::CONTENT_LENGTH, file.content_length.into());
+            if let Some(value) = file
+                .last_modified
+                .and_then(|date| http::HeaderValue::from_str(&date).ok())
+            {
+                headers.insert(http::header::LAST_MODIFIED, value);
+            }
+
+            #[cfg(feature = "content-hash")]
+            if let Ok(value) = http::HeaderValue::from_str(&file.etag) {
+                headers.insert(http::header::ETAG, value);
+
+                let is_not_modified = request
+                    .headers()
+                    .get(http::header::IF_NONE_MATCH)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|if_none_match| crate::etag_matches(if_none_match, &file.etag));
+                if is_not_modified {
+                    *res.status_mut() = http::StatusCode::NOT_MODIFIED;
+                    return res;
+                }
+            }
+            #[cfg(not(feature = "content-hash"))]
+            let _ = request;
+
+            *res.body_mut() = Box::pin(OnceBody(Some(file.bytes.into_owned().into())));
+        }
+        Err(ViteError::NotFound) => *res.status_mut() = http::StatusCode::NOT_FOUND,
+        Err(_) => *res.status_mut() = http::StatusCode::INTERNAL_SERVER_ERROR,
+    }
+
+    res
+}
+
+/// Response to an upgrade request when there's no HMR websocket to connect to.
+pub(crate) fn upgrade_not_found() -> (http::Response<()>, Option<ViteUpgraded>) {
+    let mut head = http::Response::new(());
+    *head.status_mut() = http::StatusCode::NOT_FOUND;
+    (head, None)
+}
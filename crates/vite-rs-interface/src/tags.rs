@@ -0,0 +1,55 @@
+//! Rendering of the HTML tags loading a file, e.g. for server-rendered templates.
+
+use crate::ViteFile;
+
+/// `<script type="module">` tag loading `file` from `src` (the URL it's served at, e.g.
+/// `/assets/index-BZiJcslM.js`).
+///
+/// With the `integrity` feature, release builds add the file's `integrity` hash (and
+/// `crossorigin`, which SRI requires for cross-origin files). Development builds don't: the dev
+/// server transforms files on each request, so their hashes would go stale.
+pub fn script_tag(src: &str, file: &ViteFile) -> String {
+    format!(
+        "<script type=\"module\" src=\"{}\"{}></script>",
+        escape(src),
+        integrity_attributes(file)
+    )
+}
+
+/// `<link rel="stylesheet">` tag loading `file` from `href` (the URL it's served at, e.g.
+/// `/assets/index-BPvgi06w.css`). See `script_tag` for the `integrity` hash.
+pub fn stylesheet_tag(href: &str, file: &ViteFile) -> String {
+    format!(
+        "<link rel=\"stylesheet\" href=\"{}\"{}>",
+        escape(href),
+        integrity_attributes(file)
+    )
+}
+
+#[cfg(all(
+    feature = "integrity",
+    any(not(debug_assertions), feature = "debug-prod")
+))]
+fn integrity_attributes(file: &ViteFile) -> String {
+    format!(
+        " integrity=\"{}\" crossorigin=\"anonymous\"",
+        file.integrity
+    )
+}
+
+#[cfg(not(all(
+    feature = "integrity",
+    any(not(debug_assertions), feature = "debug-prod")
+)))]
+fn integrity_attributes(_file: &ViteFile) -> String {
+    String::new()
+}
+
+/// Escapes an attribute value.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -12,8 +12,12 @@ fn main() {
     #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
     {
         // only needed for this example
-        println!("Waiting for the dev server to start (2 second)");
-        std::thread::sleep(std::time::Duration::from_secs(2));
+        println!("Waiting for the dev server to start");
+        _guard
+            .as_ref()
+            .unwrap()
+            .wait_ready(std::time::Duration::from_secs(30))
+            .unwrap();
     }
 
     #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
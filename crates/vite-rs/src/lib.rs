@@ -3,7 +3,14 @@
 #[cfg(not(doctest))] // for some reason, the cfgs above don't apply to doc tests
 pub use vite_rs_dev_server::ctrlc;
 #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
-pub use vite_rs_dev_server::{self, ViteProcess};
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub use vite_rs_dev_server::set_capture_logs;
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+pub use vite_rs_dev_server::{
+    self, set_auto_restart, set_configure_hmr, set_restart_on_config_change,
+    set_shutdown_grace_period, stop_all_dev_servers, DevServerBuilder, DevServerError, ViteProcess,
+    WaitReadyError,
+};
 pub use vite_rs_embed_macro::Embed;
 
 pub use vite_rs_interface::*;
@@ -18,6 +18,9 @@ fn main() {
             panic!("You need to enable one of the 'builtin-ctrl-c-handler' or 'custom-ctrl-c-handler' features to run this test.");
         }
 
+        // Give Vite plenty of time to exit on SIGTERM; the test asserts that it doesn't need it
+        vite_rs::set_shutdown_grace_period(std::time::Duration::from_secs(10));
+
         ///
         /// Feature: Built-in ctrl-c handling
         ///
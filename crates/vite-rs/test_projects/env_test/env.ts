@@ -0,0 +1,7 @@
+// logged so that the values aren't tree-shaken from the build
+console.log(
+  import.meta.env.VITE_API_URL,
+  import.meta.env.VITE_CARGO_PKG_VERSION,
+  import.meta.env.VITE_PROFILE,
+  import.meta.env.VITE_RUNTIME_VALUE,
+);
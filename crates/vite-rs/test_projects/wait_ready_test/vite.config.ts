@@ -0,0 +1,6 @@
+import { defineConfig } from "vite";
+
+// This config intentionally fails to load so that the dev server exits on startup.
+throw new Error("vite-rs: intentionally broken config for wait_ready_test");
+
+export default defineConfig({});
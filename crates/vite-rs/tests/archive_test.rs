@@ -0,0 +1,39 @@
+// Run with `cargo test --release --features archive --test archive_test`.
+#[cfg(all(
+    feature = "archive",
+    any(not(debug_assertions), feature = "debug-prod")
+))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+#[cfg(all(
+    feature = "archive",
+    any(not(debug_assertions), feature = "debug-prod")
+))]
+#[test]
+fn test() {
+    let output_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test_projects/normal_usage_test/dist");
+
+    // the files are packed into a single archive, next to the vite manifest
+    assert!(output_dir.join(".vite/vite-rs-archive.bin").is_file());
+
+    // each file is its own slice of the archive
+    let list = Assets::iter().collect::<Vec<_>>();
+    assert!(!list.is_empty());
+    for path in list {
+        let file = Assets::get(&path).unwrap();
+        let expected = std::fs::read(output_dir.join(&*path)).unwrap();
+
+        assert_eq!(&*file.bytes, &expected[..], "{}", path);
+        assert_eq!(file.content_length, expected.len() as u64);
+    }
+
+    // the archive itself isn't served
+    assert!(Assets::get(".vite/vite-rs-archive.bin").is_none());
+    assert_eq!(
+        Assets::get("app/pack1.ts").unwrap().bytes,
+        Assets::get("assets/pack1-B2m_tRuS.js").unwrap().bytes
+    );
+}
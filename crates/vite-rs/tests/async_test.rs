@@ -0,0 +1,43 @@
+// Run with `cargo test --features tokio --test async_test`.
+#[cfg(feature = "tokio")]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(feature = "tokio")]
+#[tokio::test(flavor = "current_thread")]
+async fn test() {
+    // a single-threaded runtime would deadlock if any of these blocked it
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    let guard = Assets::start_dev_server_async(false)
+        .await
+        .expect("start_dev_server_async should return a guard in dev builds");
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    guard
+        .wait_ready_async(std::time::Duration::from_secs(30))
+        .await
+        .expect("ViteJS dev server did not start");
+
+    let file = Assets::get_async("app/index.html")
+        .await
+        .expect("Failed to get app/index.html");
+    assert_eq!(file.content_type, "text/html");
+    assert!(std::str::from_utf8(&file.bytes)
+        .unwrap()
+        .contains("<title>vite-rs</title>"));
+
+    // through the async trait
+    let assets: Box<dyn vite_rs::GetFromViteAsync> = Assets::boxed_async();
+    let file = assets
+        .get_async("app/index.html")
+        .await
+        .expect("Failed to get app/index.html through GetFromViteAsync");
+    assert_eq!(file.content_type, "text/html");
+
+    // the ViteJS dev server serves index.html for unknown paths, so this is only checked in release
+    #[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+    assert!(assets.get_async("does-not-exist.txt").await.is_none());
+}
@@ -0,0 +1,145 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod util;
+
+// Each struct's dev server is a stand-in serving a few fixed files (see `serve`).
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+#[dev_server_url = "http://127.0.0.1:21282"]
+struct BaseAssets;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+#[dev_server_url = "http://127.0.0.1:21283"]
+struct ThemeAssets;
+
+// nothing is listening on port 1
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+#[dev_server_url = "http://127.0.0.1:1"]
+struct UnreachableAssets;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[tokio::test]
+async fn test() {
+    use vite_rs::{Fallback, GetFromVite, Overlay, Prefixed, ViteError};
+
+    serve(
+        21282,
+        &[
+            ("/index.html", "200 OK", "base index"),
+            ("/base.txt", "200 OK", "base file"),
+            ("/broken.txt", "200 OK", "base broken"),
+        ],
+    );
+    serve(
+        21283,
+        &[
+            ("/index.html", "200 OK", "theme index"),
+            ("/theme.txt?v=1", "200 OK", "theme file"),
+            ("/broken.txt", "500 Internal Server Error", "error"),
+        ],
+    );
+
+    let body = |file: vite_rs::ViteFile| String::from_utf8(file.bytes.into_owned()).unwrap();
+
+    // overlay: only missing files fall through
+    let overlay = Overlay(ThemeAssets, BaseAssets);
+    assert_eq!(body(overlay.try_get("index.html").unwrap()), "theme index");
+    assert_eq!(body(overlay.try_get("base.txt").unwrap()), "base file");
+    assert!(matches!(
+        overlay.try_get("broken.txt"),
+        Err(ViteError::DevServerStatus(status)) if status == 500
+    ));
+    assert!(overlay.try_get("missing.txt").unwrap_err().is_not_found());
+
+    // fallback: any error falls through
+    let fallback = Fallback(ThemeAssets, BaseAssets);
+    assert_eq!(body(fallback.try_get("broken.txt").unwrap()), "base broken");
+    assert!(fallback.try_get("missing.txt").unwrap_err().is_not_found());
+
+    let fallback = Fallback(UnreachableAssets, BaseAssets);
+    assert_eq!(body(fallback.try_get("index.html").unwrap()), "base index");
+    assert!(matches!(
+        fallback.try_get("missing.txt"),
+        Err(ViteError::DevServerUnreachable(_))
+    ));
+
+    // prefixed
+    let prefixed = Prefixed("admin/", ThemeAssets);
+    assert_eq!(
+        body(prefixed.try_get("admin/index.html").unwrap()),
+        "theme index"
+    );
+    assert_eq!(body(prefixed.try_get("admin/").unwrap()), "theme index");
+    assert!(prefixed.try_get("index.html").unwrap_err().is_not_found());
+
+    // combinators nest, and can be boxed like a derived struct
+    let assets: Box<dyn GetFromVite> = Box::new(Overlay(
+        Prefixed("admin/", ThemeAssets),
+        Fallback(UnreachableAssets, BaseAssets),
+    ));
+    assert_eq!(
+        body(assets.try_get("admin/index.html").unwrap()),
+        "theme index"
+    );
+    assert_eq!(body(assets.try_get("index.html").unwrap()), "base index");
+    let assets = assets.clone_box();
+    assert_eq!(body(assets.get("base.txt").unwrap()), "base file");
+
+    // proxying follows the same precedence
+    let request = |path: &str| {
+        vite_rs::vite_rs_dev_server::http::Request::get(path)
+            .body(())
+            .unwrap()
+    };
+    let res = assets.proxy(request("/admin/index.html")).await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = assets.proxy(request("/base.txt")).await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = Prefixed("admin/", BaseAssets)
+        .proxy(request("/base.txt"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 404);
+
+    // the prefix is removed before proxying, and the query is kept
+    let prefixed = Prefixed("admin/", ThemeAssets);
+    let res = prefixed
+        .proxy(request("/admin/theme.txt?v=1"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    let res = prefixed.proxy(request("/theme.txt?v=1")).await.unwrap();
+    assert_eq!(res.status(), 404);
+
+    // the prefix itself is the index, like `/`
+    let res = prefixed.proxy(request("/admin/")).await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = prefixed.proxy(request("/admin")).await.unwrap();
+    assert_eq!(res.status(), 404);
+
+    // listings are merged, without duplicates
+    let list = Overlay(BaseAssets, Prefixed("admin/", BaseAssets)).list();
+    let base_list = BaseAssets::iter().collect::<Vec<_>>();
+    assert_eq!(list.len(), base_list.len() * 2);
+    assert!(list.iter().any(|path| path == "admin/app/index.html"));
+    assert_eq!(Overlay(BaseAssets, BaseAssets).list(), base_list);
+}
+
+/// Serves a stand-in dev server on `port`, which responds with the given status and body for
+/// each path, and with `404 Not Found` for other paths.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+fn serve(port: u16, files: &'static [(&'static str, &'static str, &'static str)]) {
+    util::serve_dev_server(port, move |target| {
+        let (status, body) = files
+            .iter()
+            .find(|file| file.0 == target)
+            .map(|file| (file.1, file.2))
+            .unwrap_or(("404 Not Found", "not found"));
+
+        util::Response::text(status, body)
+    });
+}
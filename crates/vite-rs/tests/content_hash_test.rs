@@ -0,0 +1,91 @@
+// Run with `cargo test --features blake3,xxh3,in-memory --test content_hash_test` (add
+// `--release` for the embedded files' hashes).
+#[cfg(all(feature = "xxh3", any(not(debug_assertions), feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+#[content_hash = "xxh3"]
+struct Xxh3Assets;
+
+#[cfg(feature = "content-hash")]
+#[test]
+fn hash_test() {
+    use vite_rs::HashAlgorithm;
+
+    assert_eq!(HashAlgorithm::default(), HashAlgorithm::Sha256);
+    assert_eq!(
+        HashAlgorithm::Sha256.hash(b"test"),
+        "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08"
+    );
+    assert_eq!(
+        HashAlgorithm::from_name("sha256"),
+        Some(HashAlgorithm::Sha256)
+    );
+    assert_eq!(HashAlgorithm::from_name("md5"), None);
+
+    #[cfg(feature = "blake3")]
+    assert_eq!(
+        HashAlgorithm::Blake3.hash(b"test"),
+        "4878CA0425C739FA427F7EDA20FE845F6B2E46BA5FE2A14DF5B1E32F50603215"
+    );
+
+    #[cfg(feature = "xxh3")]
+    assert_eq!(
+        HashAlgorithm::Xxh3.hash(b"test"),
+        "6C78E0E3BD51D358D01E758642B85FB8"
+    );
+}
+
+#[cfg(feature = "content-hash")]
+#[test]
+fn etag_test() {
+    use vite_rs::{etag_matches, strong_etag};
+
+    let etag = strong_etag("ABC");
+    assert_eq!(etag, "\"ABC\"");
+
+    assert!(etag_matches("\"ABC\"", &etag));
+    // weak comparison: the W/ prefix is ignored
+    assert!(etag_matches("W/\"ABC\"", &etag));
+    assert!(etag_matches("\"ABC\"", "W/\"ABC\""));
+    assert!(etag_matches("\"XYZ\", W/\"ABC\"", &etag));
+    assert!(etag_matches("*", &etag));
+
+    assert!(!etag_matches("\"XYZ\"", &etag));
+    assert!(!etag_matches("ABC", &etag)); // not quoted
+    assert!(!etag_matches("\"ABC", &etag));
+    assert!(!etag_matches("", &etag));
+}
+
+#[cfg(all(feature = "xxh3", any(not(debug_assertions), feature = "debug-prod")))]
+#[test]
+fn embedded_test() {
+    use vite_rs::{strong_etag, HashAlgorithm};
+
+    // hashes are computed at compile time, for every file
+    for path in Xxh3Assets::iter() {
+        let file = Xxh3Assets::get(&path).unwrap();
+        assert_eq!(file.content_hash, HashAlgorithm::Xxh3.hash(&file.bytes));
+        assert_eq!(file.etag, strong_etag(&file.content_hash));
+    }
+}
+
+#[cfg(all(feature = "xxh3", feature = "in-memory"))]
+#[test]
+fn in_memory_test() {
+    use vite_rs::{GetFromVite, HashAlgorithm, InMemoryAssets};
+
+    let assets = InMemoryAssets::builder()
+        .insert("sha256.txt", "test")
+        .hash_algorithm(HashAlgorithm::Xxh3)
+        .insert("xxh3.txt", "test")
+        .build();
+
+    assert_eq!(
+        assets.get("sha256.txt").unwrap().content_hash,
+        HashAlgorithm::Sha256.hash(b"test")
+    );
+    assert_eq!(
+        assets.get("xxh3.txt").unwrap().content_hash,
+        HashAlgorithm::Xxh3.hash(b"test")
+    );
+}
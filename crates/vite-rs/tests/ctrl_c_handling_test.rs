@@ -34,17 +34,36 @@ mod dev_tests {
     pub fn unix_ensure_dev_server_exits_on_signal(feature: &str, signal: nix::sys::signal::Signal) {
         assert_dev_server_is_not_running();
 
-        let child = run(feature);
+        let mut child = run(feature);
 
         assert_dev_server_is_running();
 
-        send_term_signal(child, signal);
+        let signalled_at = std::time::Instant::now();
+        send_term_signal(&child, signal);
+
+        // the test project exits once the dev server has been stopped and reaped
+        child
+            .wait()
+            .expect("Failed to wait for test project to exit.");
+        let shutdown_time = signalled_at.elapsed();
+
+        // Vite should exit on SIGTERM well within the test project's grace period (10s),
+        // rather than being killed once it elapses
+        assert!(
+            shutdown_time < SHUTDOWN_GRACE_PERIOD,
+            "dev server took {:?} to shut down",
+            shutdown_time
+        );
 
         assert_dev_server_is_not_running();
     }
 
+    /// The grace period set by the test project with `vite_rs::set_shutdown_grace_period`.
+    #[cfg(unix)]
+    const SHUTDOWN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(10);
+
     #[cfg(unix)]
-    fn send_term_signal(child: std::process::Child, signal: nix::sys::signal::Signal) {
+    fn send_term_signal(child: &std::process::Child, signal: nix::sys::signal::Signal) {
         use nix::sys::signal;
         use nix::unistd::Pid;
 
@@ -0,0 +1,80 @@
+// Run in both profiles (`cargo test --test custom_source_test` with and without `--release`): an
+// asset source implementing only the required methods must compile in each.
+use std::borrow::Cow;
+
+use vite_rs::{GetFromVite, ViteError, ViteFile};
+
+const INDEX: &[u8] = b"<html>hello</html>";
+
+/// An asset source like one a downstream crate would write, with a single `index.html`.
+struct HelloAssets;
+
+impl GetFromVite for HelloAssets {
+    fn get(&self, file_path: &str) -> Option<ViteFile> {
+        self.try_get(file_path).ok()
+    }
+
+    fn try_get(&self, file_path: &str) -> Result<ViteFile, ViteError> {
+        if file_path != "index.html" {
+            return Err(ViteError::NotFound);
+        }
+
+        #[cfg(feature = "content-hash")]
+        let content_hash = vite_rs::HashAlgorithm::Sha256.hash(INDEX);
+
+        Ok(ViteFile {
+            bytes: Cow::Borrowed(INDEX),
+            last_modified: None,
+            content_type: Cow::Borrowed("text/html"),
+            content_length: INDEX.len() as u64,
+            #[cfg(feature = "content-hash")]
+            etag: Cow::Owned(vite_rs::strong_etag(&content_hash)),
+            #[cfg(feature = "content-hash")]
+            content_hash: Cow::Owned(content_hash),
+            #[cfg(feature = "integrity")]
+            integrity: vite_rs::Integrity::compute(INDEX),
+        })
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'static, str>>> {
+        Box::new(std::iter::once(Cow::Borrowed("index.html")))
+    }
+
+    fn clone_box(&self) -> Box<dyn GetFromVite> {
+        Box::new(HelloAssets)
+    }
+}
+
+#[test]
+fn get_test() {
+    let assets: Box<dyn GetFromVite> = Box::new(HelloAssets);
+
+    assert_eq!(assets.get("index.html").unwrap().bytes, INDEX);
+    assert!(assets.try_get("missing.html").unwrap_err().is_not_found());
+    assert_eq!(assets.list(), vec!["index.html"]);
+}
+
+/// Without a dev server to forward to, requests are answered with the files from `try_get`.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[tokio::test]
+async fn default_proxy_test() {
+    use vite_rs::vite_rs_dev_server::http::{Request, StatusCode};
+
+    let request = |path: &str| Request::get(path).body(()).unwrap();
+
+    let res = HelloAssets.proxy(request("/")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "text/html");
+    #[cfg(feature = "content-hash")]
+    assert_eq!(
+        res.headers()["etag"],
+        *HelloAssets.get("index.html").unwrap().etag
+    );
+
+    let res = HelloAssets.proxy(request("/missing.html")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let (head, upgraded) = HelloAssets.proxy_upgrade(request("/")).await.unwrap();
+    assert_eq!(head.status(), StatusCode::NOT_FOUND);
+    assert!(upgraded.is_none());
+}
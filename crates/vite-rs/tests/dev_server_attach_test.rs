@@ -0,0 +1,47 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod util;
+
+// No `#[dev_server_port]`: a dev server started by hand is looked for at the start of the default
+// port range.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+/// Note: we only have a single #[test] because the stand-in dev server holds the default port.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    let root =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_projects/normal_usage_test");
+    // a stand-in for a dev server started by hand in the project: it serves the project's files,
+    // which is how a dev server running for the project is recognized
+    let served_root = root.clone();
+    util::serve_dev_server(
+        vite_rs::vite_rs_dev_server::DEFAULT_PORT_RANGE.start,
+        move |target| {
+            let path = target.split('?').next().unwrap_or_default();
+            match std::fs::read(served_root.join(path.trim_start_matches('/'))) {
+                Ok(body) => util::Response::text("200 OK", body),
+                Err(_) => util::Response::text("404 Not Found", ""),
+            }
+        },
+    );
+
+    let guard = Assets::try_start_dev_server(false).expect("Failed to start the dev server");
+    // attached: the guard doesn't own the dev server, so no Vite was spawned
+    assert!(guard.child.is_none());
+    assert!(Assets::is_dev_server_running());
+
+    // the marker files served to recognize the dev server are removed, and kept out of git
+    let work_dir = root.join(".vite/vite-rs");
+    assert!(work_dir.join(".gitignore").is_file());
+    let markers = std::fs::read_dir(&work_dir)
+        .unwrap()
+        .filter(|entry| {
+            let file_name = entry.as_ref().unwrap().file_name();
+            file_name.to_string_lossy().starts_with("signature-")
+        })
+        .count();
+    assert_eq!(markers, 0);
+}
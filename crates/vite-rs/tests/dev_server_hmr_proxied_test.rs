@@ -0,0 +1,41 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+#[dev_server_port = "21253"]
+struct Assets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    use vite_rs::GetFromVite;
+
+    let generated_config = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test_projects/normal_usage_test/.vite/vite-rs/hmr-21253.config.mjs");
+    let read_config = || std::fs::read_to_string(&generated_config).unwrap();
+
+    {
+        let _guard = Assets::start_dev_server(false)
+            .expect("start_dev_server should return a guard in dev builds");
+
+        // Test that the browser is pointed at the dev server's port by default
+        assert!(read_config().contains("port: 21253"));
+
+        // Test that the generated files stay out of version control
+        let gitignore = generated_config.parent().unwrap().join(".gitignore");
+        assert!(std::fs::read_to_string(gitignore).unwrap().contains('*'));
+
+        // Test that `ViteServe` proxying the websocket rewrites the running dev server's config
+        Assets::boxed().set_hmr_proxied();
+        assert!(!read_config().contains("port: 21253"));
+        assert!(read_config().contains("import userConfig from \"../../vite.config.ts\";"));
+    }
+
+    assert!(!generated_config.exists());
+
+    // Test that dev servers started afterwards leave `server.hmr.port` alone
+    let _guard = Assets::start_dev_server(false)
+        .expect("start_dev_server should return a guard in dev builds");
+    assert!(!generated_config.exists());
+}
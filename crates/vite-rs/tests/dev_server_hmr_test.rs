@@ -0,0 +1,40 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+#[dev_server_port = "21252"]
+struct Assets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    let generated_config = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test_projects/custom_dev_server_port_test/.vite/vite-rs/hmr-21252.config.mjs");
+
+    {
+        let guard = Assets::start_dev_server(false)
+            .expect("start_dev_server should return a guard in dev builds");
+
+        guard
+            .wait_ready(std::time::Duration::from_secs(30))
+            .expect("ViteJS dev server did not start");
+
+        assert!(generated_config.exists());
+
+        // Test that Vite's HMR client connects to the dev server's port
+        let client = Assets::get("@vite/client").expect("Failed to get Vite's HMR client");
+        assert!(std::str::from_utf8(&client.bytes)
+            .unwrap()
+            .contains("const hmrPort = 21252;"));
+
+        // Test that the project's own config is still applied
+        let file = Assets::get("index.html").expect("Failed to get index.html");
+        assert!(std::str::from_utf8(&file.bytes)
+            .unwrap()
+            .contains("Custom Dev Server Port Test"));
+    }
+
+    // Test that the generated config is removed when the dev server stops
+    assert!(!generated_config.exists());
+}
@@ -0,0 +1,90 @@
+// Run with `cargo test --features log --test dev_server_logs_test`.
+//
+// This project's Vite config throws on load, so the dev server logs an error and exits.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/wait_ready_test"]
+struct BrokenConfigAssets;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+mod dev_tests {
+    use std::sync::Mutex;
+
+    /// Collects every record logged with the `vite` target.
+    pub struct ViteLogger {
+        pub records: Mutex<Vec<(log::Level, String)>>,
+    }
+
+    impl log::Log for ViteLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == "vite"
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.records
+                    .lock()
+                    .unwrap()
+                    .push((record.level(), record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    pub static LOGGER: ViteLogger = ViteLogger {
+        records: Mutex::new(Vec::new()),
+    };
+}
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+#[test]
+fn test() {
+    use dev_tests::LOGGER;
+
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    vite_rs::set_capture_logs(true);
+
+    #[cfg(feature = "ctrlc")]
+    let guard = BrokenConfigAssets::start_dev_server(false);
+    #[cfg(not(feature = "ctrlc"))]
+    let guard = BrokenConfigAssets::start_dev_server();
+
+    let guard = guard.expect("start_dev_server should return a guard in dev builds");
+
+    assert!(matches!(
+        guard.wait_ready(std::time::Duration::from_secs(30)),
+        Err(vite_rs::WaitReadyError::Exited(_))
+    ));
+
+    // the dev server's output is forwarded from separate threads
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    loop {
+        let records = LOGGER.records.lock().unwrap();
+
+        if records.iter().any(|(level, _)| *level == log::Level::Error) {
+            for (_, message) in records.iter() {
+                assert!(message.starts_with("[vite]") || message.contains(" [vite] "));
+                assert!(!message.contains('\x1b'), "ANSI codes were not stripped");
+            }
+
+            break;
+        }
+
+        assert!(
+            std::time::Instant::now() < deadline,
+            "expected the dev server's error to be logged, got: {:#?}",
+            *records
+        );
+
+        drop(records);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+struct Assets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    std::env::set_var(vite_rs::vite_rs_dev_server::PORT_ENV_VAR, "21242");
+
+    // Test that the environment variable is honored when the dev server starts
+    {
+        let _listener = std::net::TcpListener::bind("0.0.0.0:21242")
+            .expect("Failed to bind to port 21242 for this test");
+
+        match Assets::try_start_dev_server(false) {
+            Err(vite_rs::DevServerError::PortInUse(port)) => assert_eq!(port, 21242),
+            Err(e) => panic!("expected the port to be in use, got: {}", e),
+            Ok(_) => panic!("expected the port to be in use, but the dev server started"),
+        }
+    }
+
+    // Test that the generated code uses the port the dev server bound
+    {
+        let guard = Assets::start_dev_server(false)
+            .expect("start_dev_server should return a guard in dev builds");
+
+        guard
+            .wait_ready(std::time::Duration::from_secs(30))
+            .expect("ViteJS dev server did not start");
+
+        std::env::remove_var(vite_rs::vite_rs_dev_server::PORT_ENV_VAR);
+
+        let file = Assets::get("index.html").expect("Failed to get index.html");
+        assert!(std::str::from_utf8(&file.bytes)
+            .unwrap()
+            .contains("Custom Dev Server Port Test"));
+    }
+}
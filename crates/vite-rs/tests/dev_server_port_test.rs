@@ -26,12 +26,27 @@ fn test_dev_server_port() {
 
     #[cfg(unix)]
     {
+        // Rust-managed: Test that a busy port is reported instead of panicking
+        {
+            let _listener = std::net::TcpListener::bind("0.0.0.0:21222")
+                .expect("Failed to bind to port 21222 for this test");
+
+            match AssetsWithoutQuotes::try_start_dev_server(false) {
+                Err(vite_rs::DevServerError::PortInUse(port)) => assert_eq!(port, 21222),
+                Err(e) => panic!("expected the port to be in use, got: {}", e),
+                Ok(_) => panic!("expected the port to be in use, but the dev server started"),
+            }
+        }
+
         // Rust-managed: Test that the dev server runs on the specified port
         {
             let _guard = AssetsWithoutQuotes::start_dev_server(false);
 
-            // we wait 2 seconds to make sure the dev server has time to start
-            std::thread::sleep(std::time::Duration::from_secs(2));
+            _guard
+                .as_ref()
+                .unwrap()
+                .wait_ready(std::time::Duration::from_secs(30))
+                .expect("ViteJS dev server did not start");
 
             match assert_dev_server_running_on_port(21222) {
                 Ok(resp) => {
@@ -49,8 +64,11 @@ fn test_dev_server_port() {
 
             let _guard = Assets::start_dev_server(false);
 
-            // we wait 2 seconds to make sure the dev server has time to start
-            std::thread::sleep(std::time::Duration::from_secs(2));
+            _guard
+                .as_ref()
+                .unwrap()
+                .wait_ready(std::time::Duration::from_secs(30))
+                .expect("ViteJS dev server did not start");
 
             match assert_dev_server_running_on_port(21232) {
                 Ok(resp) => {
@@ -88,6 +106,20 @@ fn test_dev_server_port() {
             // we wait 2 seconds to make sure the dev server has time to start
             std::thread::sleep(std::time::Duration::from_secs(2));
 
+            // Rust-managed: Test that we attach to the self-managed dev server since it runs for the same project
+            let attached = Assets::is_dev_server_running()
+                && !AssetsWithoutQuotes::is_dev_server_running() // different port
+                && match Assets::try_start_dev_server(false) {
+                    // the guard doesn't own the dev server, so dropping it shouldn't stop it
+                    Ok(guard) => guard.child.is_none(),
+                    Err(_) => false,
+                };
+
+            if !attached {
+                close_dev_server();
+                panic!("expected to attach to the self-managed dev server");
+            }
+
             match assert_dev_server_running_on_port(21232) {
                 Ok(resp) => {
                     close_dev_server();
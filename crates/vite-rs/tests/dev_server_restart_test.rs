@@ -0,0 +1,40 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    let guard = Assets::start_dev_server(false)
+        .expect("start_dev_server should return a guard in dev builds");
+
+    guard
+        .wait_ready(std::time::Duration::from_secs(30))
+        .expect("ViteJS dev server did not start");
+
+    assert!(Assets::get("app/index.html").is_some());
+
+    // simulate a crash
+    guard
+        .child
+        .as_ref()
+        .expect("the dev server should be managed by vite-rs")
+        .lock()
+        .unwrap()
+        .kill()
+        .expect("Failed to kill the ViteJS dev server");
+
+    // the supervisor should restart it after a short backoff
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+    while Assets::get("app/index.html").is_none() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "ViteJS dev server was not restarted"
+        );
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod util;
+
+// The dev server URL is overridden with `VITE_RS_DEV_SERVER_URL` below, so this never resolves.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+#[dev_server_url = "http://127.0.0.1:1"]
+struct RemoteAssets;
+
+/// Note: we only have a single #[test] because the URL is overridden with an environment variable.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    // start_dev_server is a no-op for remote dev servers
+    let guard = RemoteAssets::start_dev_server(false)
+        .expect("start_dev_server should return a guard in dev builds");
+    assert!(guard.child.is_none());
+    assert!(!RemoteAssets::is_dev_server_running());
+
+    // requests go to the remote dev server (the environment variable takes precedence)
+    // a stand-in dev server which echoes each request
+    let remote_url = util::serve_dev_server(0, |target| {
+        util::Response::text("200 OK", format!("GET {}", target))
+    });
+    std::env::set_var(
+        vite_rs::vite_rs_dev_server::URL_ENV_VAR,
+        format!("{}/", remote_url),
+    );
+
+    let file = RemoteAssets::get("remote.txt").expect("Failed to get remote.txt");
+    assert_eq!(&*file.bytes, b"GET /remote.txt");
+    assert_eq!(file.content_type, "text/plain");
+
+    std::env::remove_var(vite_rs::vite_rs_dev_server::URL_ENV_VAR);
+}
@@ -0,0 +1,58 @@
+// Run with `cargo test --features directory --test directory_assets_test`.
+#[cfg(feature = "directory")]
+#[test]
+fn test() {
+    use vite_rs::{DirectoryAssets, GetFromVite, ViteError};
+
+    // a prebuilt ViteJS output directory, so no build is needed
+    let assets = DirectoryAssets::new(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_projects/directory_assets_test/build"),
+    );
+
+    let index = assets
+        .try_get("index.html")
+        .expect("Failed to get index.html");
+    assert_eq!(index.content_type, "text/html");
+    assert_eq!(index.content_length, index.bytes.len() as u64);
+    assert!(index.last_modified.is_some());
+
+    // entrypoints are aliased to their compiled file
+    let main = assets
+        .try_get("src/main.ts")
+        .expect("Failed to get src/main.ts");
+    assert_eq!(&*main.bytes, b"console.log(\"main\");\n");
+    assert_eq!(main.content_type, "application/javascript");
+    assert_eq!(
+        &*assets.get("assets/main-Cx3tQ1dq.js").unwrap().bytes,
+        &*main.bytes
+    );
+
+    // only entrypoints are aliased
+    assert!(assets.try_get("src/lazy.ts").unwrap_err().is_not_found());
+
+    // paths can't leave the directory or read vite's internal files
+    assert!(matches!(
+        assets.try_get("../../Cargo.toml"),
+        Err(ViteError::NotFound)
+    ));
+    assert!(assets.try_get("/etc/hostname").unwrap_err().is_not_found());
+    assert!(assets
+        .try_get(".vite/manifest.json")
+        .unwrap_err()
+        .is_not_found());
+
+    assert_eq!(
+        assets.list(),
+        ["assets/main-Cx3tQ1dq.js", "index.html", "robots.txt"]
+    );
+
+    #[cfg(feature = "content-hash")]
+    {
+        use sha2::{Digest, Sha256};
+
+        let hash = format!("{:X}", Sha256::digest(&index.bytes));
+        assert_eq!(index.content_hash, hash);
+        assert_eq!(index.etag, format!("\"{}\"", hash));
+    }
+}
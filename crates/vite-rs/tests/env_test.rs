@@ -0,0 +1,39 @@
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/env_test"]
+#[env(VITE_API_URL = "https://api.example.com")]
+#[forward_env(CARGO_PKG_VERSION, PROFILE)]
+struct Assets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[test]
+pub fn test() {
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    let _guard = Assets::dev_server()
+        .env("VITE_RUNTIME_VALUE", "set-at-runtime")
+        .start(true);
+
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    _guard
+        .as_ref()
+        .unwrap()
+        .wait_ready(std::time::Duration::from_secs(30))
+        .expect("ViteJS dev server did not start");
+
+    let file = Assets::get("env.ts").expect("Failed to get env.ts");
+    let code = std::str::from_utf8(&file.bytes).unwrap();
+
+    // #[env(...)]
+    assert!(code.contains("https://api.example.com"));
+
+    // #[forward_env(...)]
+    assert!(code.contains(env!("CARGO_PKG_VERSION")));
+    #[cfg(debug_assertions)]
+    assert!(code.contains("debug"));
+    #[cfg(not(debug_assertions))]
+    assert!(code.contains("release"));
+
+    // `dev_server().env(...)`
+    #[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+    assert!(code.contains("set-at-runtime"));
+}
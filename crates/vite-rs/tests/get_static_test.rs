@@ -0,0 +1,26 @@
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+#[test]
+fn get_static_test() {
+    for path in Assets::iter() {
+        let file = Assets::get_static(&path).unwrap();
+
+        // the same embedded file is returned each time, without copying it
+        assert!(std::ptr::eq(file, Assets::get_static(&path).unwrap()));
+        assert_eq!(file.bytes, Assets::get(&path).unwrap().bytes);
+    }
+
+    // aliases resolve to the compiled file
+    assert!(std::ptr::eq(
+        Assets::get_static("app/pack1.ts").unwrap(),
+        Assets::get_static("assets/pack1-B2m_tRuS.js").unwrap()
+    ));
+
+    assert!(Assets::get_static("missing.txt").is_none());
+    assert!(Assets::get_static("").is_none());
+    assert!(Assets::get_static(".vite/manifest.json").is_none());
+}
@@ -0,0 +1,62 @@
+// Run with `cargo test --features integrity --test integrity_test` (add `--release` for the
+// embedded files' hashes).
+#[cfg(all(
+    feature = "integrity",
+    any(not(debug_assertions), feature = "debug-prod")
+))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+#[cfg(feature = "integrity")]
+#[test]
+fn compute_test() {
+    let integrity = vite_rs::Integrity::compute(b"test");
+
+    assert_eq!(
+        integrity.sha256,
+        "sha256-n4bQgYhMfWWaL+qgxVrQFaO/TxsrC4Is0V1sFbDwCgg="
+    );
+    assert_eq!(
+        integrity.sha384,
+        "sha384-doQSMg97CqWBL85CjcRwazyuUOAqZMqhangiSb/o78S37xzLEmJV0ZYEff7fF6Cp"
+    );
+    assert_eq!(integrity.to_string(), integrity.sha384);
+}
+
+#[cfg(all(
+    feature = "integrity",
+    any(not(debug_assertions), feature = "debug-prod")
+))]
+#[test]
+fn embedded_test() {
+    use vite_rs::Integrity;
+
+    // public/test.txt contains "test"
+    assert_eq!(
+        Assets::integrity("test.txt").unwrap(),
+        Integrity::compute(b"test")
+    );
+
+    // hashes are computed at compile time, for every file
+    for path in Assets::iter() {
+        let file = Assets::get(&path).unwrap();
+        assert_eq!(file.integrity, Integrity::compute(&file.bytes));
+    }
+
+    assert_eq!(
+        Assets::integrity("app/pack1.ts"),
+        Assets::integrity("assets/pack1-B2m_tRuS.js")
+    );
+    assert!(Assets::integrity("missing.js").is_none());
+
+    // tags loading the files carry their hash
+    let file = Assets::get("assets/pack1-B2m_tRuS.js").unwrap();
+    assert_eq!(
+        vite_rs::script_tag("/assets/pack1-B2m_tRuS.js", &file),
+        format!(
+            "<script type=\"module\" src=\"/assets/pack1-B2m_tRuS.js\" integrity=\"{}\" crossorigin=\"anonymous\"></script>",
+            file.integrity.sha384
+        )
+    );
+}
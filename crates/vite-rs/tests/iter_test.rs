@@ -0,0 +1,110 @@
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn dev_iter_test() {
+    // no dev server is needed; the project is scanned
+    let list = Assets::iter().collect::<Vec<_>>();
+
+    // the entry points named in `vite.config.ts`, which release builds resolve through aliases,
+    // and the public files; `app/index.ts` and the others are only reached through an entry point
+    assert_eq!(
+        list,
+        [
+            "app/index.html",
+            "app/pack1.ts",
+            "test.txt", // public/test.txt is served at /test.txt
+        ]
+    );
+
+    // the same listing is available to code using `Box<dyn GetFromVite>`
+    let assets: Box<dyn vite_rs::GetFromVite> = Assets::boxed();
+    assert_eq!(assets.list(), list);
+    assert_eq!(assets.iter().count(), list.len());
+}
+
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+#[test]
+fn iter_test() {
+    let assets: Box<dyn vite_rs::GetFromVite> = Assets::boxed();
+    assert_eq!(assets.list(), Assets::iter().collect::<Vec<_>>());
+    assert!(assets.list().iter().all(|path| assets.get(path).is_some()));
+
+    // what's listed in dev builds is available too
+    for path in ["app/index.html", "app/pack1.ts", "test.txt"] {
+        assert!(assets.get(path).is_some(), "{} should be embedded", path);
+    }
+}
+
+/// Only the entry points named in the Vite config (or `index.html`) and the public files are
+/// listed, not every file in the project.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn dev_iter_entry_points_test() {
+    let root = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("dev_iter_project");
+    let _ = std::fs::remove_dir_all(&root);
+
+    for file in [
+        "index.html",
+        "admin/index.html",
+        "src/main.ts",
+        "src/lib.rs",
+        "README.md",
+        "LICENSE",
+        "examples/demo.html",
+        "public/robots.txt",
+        "public/package.json", // served from the public directory as-is
+        "package.json",
+        "tsconfig.json",
+        "Cargo.toml",
+        "target/debug/app",
+        "dist/index.html",
+        "node_modules/vite/index.js",
+    ] {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+
+    let list = || vite_rs::vite_rs_dev_server::list_files(root.to_str().unwrap(), "dist");
+
+    // without a config (or one that names no entry points), Vite's default entry point
+    assert_eq!(list(), ["index.html", "package.json", "robots.txt"]);
+
+    std::fs::write(
+        root.join("vite.config.ts"),
+        r#"import { resolve } from "path";
+import tsconfigPaths from 'vite-tsconfig-paths';
+
+export default {
+  plugins: [tsconfigPaths({ projects: ["./tsconfig.json"] })],
+  build: {
+    rollupOptions: {
+      input: {
+        main: "./index.html",
+        admin: resolve(__dirname, 'admin/index.html'),
+        worker: `src/main.ts`,
+        other: "../outside.html",
+        built: "dist/index.html",
+      },
+    },
+    outDir: "dist",
+  },
+};
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        list(),
+        [
+            "admin/index.html",
+            "index.html",
+            "package.json",
+            "robots.txt",
+            "src/main.ts"
+        ]
+    );
+}
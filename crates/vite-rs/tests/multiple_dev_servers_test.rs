@@ -0,0 +1,71 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+#[dev_server_port = "21262"]
+struct SiteAssets;
+
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+#[dev_server_port = "21272"]
+struct AdminAssets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    use std::time::Duration;
+
+    let is_running = |guard: &vite_rs::ViteProcess| {
+        matches!(
+            guard.child.as_ref().unwrap().lock().unwrap().try_wait(),
+            Ok(None)
+        )
+    };
+
+    // Test that both dev servers run side by side; the Ctrl-C handler is only registered once
+    let site = SiteAssets::start_dev_server(true).expect("Failed to start the site dev server");
+    let admin = AdminAssets::start_dev_server(true).expect("Failed to start the admin dev server");
+
+    site.wait_ready(Duration::from_secs(30))
+        .expect("Site dev server did not start");
+    admin
+        .wait_ready(Duration::from_secs(30))
+        .expect("Admin dev server did not start");
+
+    assert!(is_running(&site));
+    assert!(is_running(&admin));
+
+    let file = SiteAssets::get("app/index.html").expect("Failed to get app/index.html");
+    assert!(std::str::from_utf8(&file.bytes)
+        .unwrap()
+        .contains("<title>vite-rs</title>"));
+
+    let file = AdminAssets::get("index.html").expect("Failed to get index.html");
+    assert!(std::str::from_utf8(&file.bytes)
+        .unwrap()
+        .contains("Custom Dev Server Port Test"));
+
+    // Test that `stop_dev_server` only stops the struct's own dev server
+    AdminAssets::stop_dev_server();
+    assert!(!is_running(&admin));
+    assert!(is_running(&site));
+    assert!(SiteAssets::get("app/index.html").is_some());
+
+    // Test that a guard only stops its own dev server
+    let admin =
+        AdminAssets::start_dev_server(false).expect("Failed to restart the admin dev server");
+    admin
+        .wait_ready(Duration::from_secs(30))
+        .expect("Admin dev server did not restart");
+
+    let site_child = site.child.clone().unwrap();
+    drop(site);
+    assert!(matches!(site_child.lock().unwrap().try_wait(), Ok(Some(_))));
+    assert!(is_running(&admin));
+
+    // Test that all dev servers can be stopped at once
+    vite_rs::stop_all_dev_servers();
+    assert!(!is_running(&admin));
+}
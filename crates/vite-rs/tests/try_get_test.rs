@@ -0,0 +1,73 @@
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+mod util;
+
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/normal_usage_test"]
+struct Assets;
+
+// The dev server URL is overridden with `VITE_RS_DEV_SERVER_URL` below.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/custom_dev_server_port_test"]
+#[dev_server_url = "http://127.0.0.1:1"]
+struct RemoteAssets;
+
+#[cfg(any(not(debug_assertions), feature = "debug-prod"))]
+#[test]
+fn embedded_test() {
+    assert!(Assets::try_get("app/index.html").is_ok());
+
+    let err = Assets::try_get("missing.txt").expect_err("missing.txt should not be embedded");
+    assert!(matches!(err, vite_rs::ViteError::NotFound));
+}
+
+/// Note: we only have a single #[test] because the URL is overridden with an environment variable.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn dev_server_test() {
+    use vite_rs::ViteError;
+
+    // nothing is listening on port 1
+    let err = RemoteAssets::try_get("ok.txt").expect_err("the dev server should be unreachable");
+    assert!(matches!(err, ViteError::DevServerUnreachable(_)));
+    assert!(RemoteAssets::get("ok.txt").is_none());
+
+    // a stand-in dev server which fails in a different way for each path
+    let remote_url = util::serve_dev_server(0, |target| match target {
+        "/missing.txt" => util::Response::text("404 Not Found", "ok"),
+        "/broken.txt" => util::Response::text("500 Internal Server Error", "ok"),
+        "/untyped.txt" => util::Response {
+            status: "200 OK",
+            content_type: None,
+            body: b"ok".to_vec(),
+        },
+        _ => util::Response::text("200 OK", "ok"),
+    });
+    std::env::set_var(
+        vite_rs::vite_rs_dev_server::URL_ENV_VAR,
+        format!("{}/", remote_url),
+    );
+
+    let file = RemoteAssets::try_get("ok.txt").expect("Failed to get ok.txt");
+    assert_eq!(&*file.bytes, b"ok");
+    assert_eq!(file.content_length, 2);
+
+    let err = RemoteAssets::try_get("missing.txt").expect_err("missing.txt should not exist");
+    assert!(err.is_not_found());
+
+    let err = RemoteAssets::try_get("broken.txt").expect_err("broken.txt should fail");
+    assert!(matches!(
+        err,
+        ViteError::DevServerStatus(status) if status == 500
+    ));
+    assert!(RemoteAssets::get("broken.txt").is_none());
+
+    let err = RemoteAssets::try_get("untyped.txt").expect_err("untyped.txt should fail");
+    assert!(matches!(
+        err,
+        ViteError::MissingHeader(ref header) if header == "content-type"
+    ));
+
+    std::env::remove_var(vite_rs::vite_rs_dev_server::URL_ENV_VAR);
+}
@@ -0,0 +1,59 @@
+use std::io::{BufRead, BufReader, Write};
+
+/// A response of the stand-in dev server.
+pub struct Response {
+    pub status: &'static str,
+    /// `None` to leave out the `Content-Type` header.
+    pub content_type: Option<&'static str>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// A `text/plain` response.
+    pub fn text(status: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Response {
+            status,
+            content_type: Some("text/plain"),
+            body: body.into(),
+        }
+    }
+}
+
+/// Serves a stand-in for a ViteJS dev server on `port` (`0` for any free port), and returns
+/// its URL (without a trailing `/`).
+///
+/// Each request is answered with `respond(target)`, where `target` is the requested path and
+/// query (e.g. `/theme.txt?v=1`). Like Vite, responses carry a weak `ETag`.
+pub fn serve_dev_server(port: u16, respond: impl Fn(&str) -> Response + Send + 'static) -> String {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .unwrap_or_else(|e| panic!("Could not listen on port {}: {}", port, e));
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(&stream);
+            reader.read_line(&mut request_line).unwrap();
+
+            let target = request_line.split(' ').nth(1).unwrap_or("/");
+            let response = respond(target);
+
+            let content_type = response
+                .content_type
+                .map(|content_type| format!("Content-Type: {}\r\n", content_type))
+                .unwrap_or_default();
+
+            write!(
+                &stream,
+                "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nETag: W/\"stand-in\"\r\nConnection: close\r\n\r\n",
+                response.status,
+                content_type,
+                response.body.len()
+            )
+            .unwrap();
+            (&stream).write_all(&response.body).unwrap();
+        }
+    });
+
+    url
+}
@@ -0,0 +1,20 @@
+// This project's Vite config throws on load, so it can't be built in release mode.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[derive(vite_rs::Embed)]
+#[root = "./test_projects/wait_ready_test"]
+struct BrokenConfigAssets;
+
+/// Note: we only have a single #[test] because we can't run multiple tests in parallel
+/// since the vite dev server can't be started multiple times.
+#[cfg(all(debug_assertions, not(feature = "debug-prod")))]
+#[test]
+fn test() {
+    let guard = BrokenConfigAssets::start_dev_server(false)
+        .expect("start_dev_server should return a guard in dev builds");
+
+    match guard.wait_ready(std::time::Duration::from_secs(30)) {
+        Err(vite_rs::WaitReadyError::Exited(_)) => { /* expected */ }
+        Err(e) => panic!("expected the dev server to exit, got: {}", e),
+        Ok(()) => panic!("expected the dev server to exit, but it became ready"),
+    }
+}